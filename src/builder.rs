@@ -0,0 +1,492 @@
+use crate::{
+    key_map::{replace_first_key, KeyMap},
+    select::Select,
+    theme::Theme,
+    SelectError,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style::Color;
+use std::fmt::Display;
+use std::io::Write;
+use std::time::Duration;
+
+/// The configuration of a select dialog without the items.
+///
+/// Define the look, the keys and the behavior once and create a `Select` for different items with `items`,
+/// `items_from_iter` or `items_with_label`. Options that depend on the items, like disabled items, groups
+/// or handlers, are set on the created `Select`.
+///
+/// A `Select` keeps its options in such a configuration as well, its setters change it the same way.
+///
+/// ```no_run
+/// use cli_select::{Select, SelectBuilder};
+/// use std::io::stdout;
+///
+/// let mut builder = SelectBuilder::new();
+/// builder.title("Choose a color").wrap_around();
+///
+/// let colors = vec!["red", "green"];
+/// let color = builder.items(&colors, stdout()).start();
+///
+/// let sizes = vec!["small", "large"];
+/// let size = builder.items(&sizes, stdout()).start();
+/// ```
+#[derive(Clone, Debug)]
+pub struct SelectBuilder {
+    pub(crate) key_map: KeyMap,
+    pub(crate) theme: Theme,
+    pub(crate) title: Option<String>,
+    pub(crate) help_text: Option<String>,
+    pub(crate) wrap_around: bool,
+    pub(crate) filterable: bool,
+    pub(crate) with_numbers: bool,
+    pub(crate) type_ahead: bool,
+    pub(crate) mouse: bool,
+    pub(crate) alternate_screen: bool,
+    pub(crate) clear_on_exit: bool,
+    pub(crate) no_color: bool,
+    pub(crate) position_indicator: bool,
+    pub(crate) page_size: usize,
+    pub(crate) visible_rows: Option<usize>,
+    pub(crate) max_visible: Option<usize>,
+    pub(crate) scroll_off: usize,
+    pub(crate) multi_select: bool,
+    pub(crate) per_page: Option<usize>,
+    pub(crate) max_width: Option<usize>,
+    pub(crate) bordered: bool,
+    pub(crate) accessible: bool,
+    pub(crate) horizontal: bool,
+    pub(crate) confirm_twice: bool,
+    pub(crate) number_selects_immediately: bool,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) hard_timeout: bool,
+    pub(crate) timeout_cancels: bool,
+    pub(crate) throttle: Option<Duration>,
+    pub(crate) min_selections: usize,
+    pub(crate) max_selections: Option<usize>,
+    pub(crate) editable: bool,
+    pub(crate) min_items: usize,
+    pub(crate) headless: Option<usize>,
+    pub(crate) submit_on_single_item: bool,
+    pub(crate) report_prefix: Option<String>,
+}
+
+impl Default for SelectBuilder {
+    fn default() -> Self {
+        SelectBuilder {
+            key_map: KeyMap::default(),
+            theme: Theme::default(),
+            title: None,
+            help_text: None,
+            wrap_around: false,
+            filterable: false,
+            with_numbers: false,
+            type_ahead: false,
+            mouse: false,
+            alternate_screen: false,
+            clear_on_exit: false,
+            no_color: false,
+            position_indicator: false,
+            page_size: 10,
+            visible_rows: None,
+            max_visible: None,
+            scroll_off: 0,
+            multi_select: false,
+            per_page: None,
+            max_width: None,
+            bordered: false,
+            accessible: false,
+            horizontal: false,
+            confirm_twice: false,
+            number_selects_immediately: false,
+            timeout: None,
+            hard_timeout: false,
+            timeout_cancels: false,
+            throttle: None,
+            min_selections: 0,
+            max_selections: None,
+            editable: false,
+            min_items: 1,
+            headless: None,
+            submit_on_single_item: false,
+            report_prefix: None,
+        }
+    }
+}
+
+impl SelectBuilder {
+    /// Create a configuration with the default settings of `Select`
+    pub fn new() -> Self {
+        SelectBuilder::default()
+    }
+    /// Create a Select Dialog for the items with this configuration. The configuration can be used again afterwards.
+    pub fn items<'a, I, W>(&self, items: &'a [I], out: W) -> Select<'a, I, W>
+    where
+        I: Display + 'a,
+        W: Write,
+    {
+        let mut select = Select::new(items, out);
+        select.apply(self);
+        select
+    }
+    /// Like `items`, but the created dialog owns the items collected from the iterator, see `Select::from_iter`
+    pub fn items_from_iter<'a, I, W>(
+        &self,
+        items: impl IntoIterator<Item = I>,
+        out: W,
+    ) -> Select<'a, I, W>
+    where
+        I: Display + 'a,
+        W: Write,
+    {
+        let mut select = Select::from_iter(items, out);
+        select.apply(self);
+        select
+    }
+    /// Like `items` for items that don't implement `Display`, see `Select::with_label`
+    pub fn items_with_label<'a, I, W>(
+        &self,
+        items: &'a [I],
+        out: W,
+        label: impl Fn(&I) -> String + 'a,
+    ) -> Select<'a, I, W>
+    where
+        W: Write,
+    {
+        let mut select = Select::with_label(items, out, label);
+        select.apply(self);
+        select
+    }
+    /// Like `Select::key_map`. Fails if enter moves the selection up or down while it confirms the selection.
+    pub fn key_map(&mut self, key_map: KeyMap) -> Result<&mut Self, SelectError> {
+        key_map.check()?;
+        self.key_map = key_map;
+        Ok(self)
+    }
+    /// Like `Select::theme`
+    pub fn theme(&mut self, theme: Theme) -> &mut Self {
+        self.theme = theme;
+        self
+    }
+    /// Like `Select::title`
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Self {
+        self.title = Some(title.into());
+        self
+    }
+    /// Like `Select::help_text`
+    pub fn help_text(&mut self, help_text: impl Into<String>) -> &mut Self {
+        self.help_text = Some(help_text.into());
+        self
+    }
+    /// Like `Select::wrap_around`
+    pub fn wrap_around(&mut self) -> &mut Self {
+        self.wrap_around = true;
+        self
+    }
+    /// Like `Select::filterable`
+    pub fn filterable(&mut self) -> &mut Self {
+        self.filterable = true;
+        self
+    }
+    /// Like `Select::with_numbers`
+    pub fn with_numbers(&mut self) -> &mut Self {
+        self.with_numbers = true;
+        self
+    }
+    /// Like `Select::type_ahead`
+    pub fn type_ahead(&mut self) -> &mut Self {
+        self.type_ahead = true;
+        self
+    }
+    /// Like `Select::mouse`
+    pub fn mouse(&mut self) -> &mut Self {
+        self.mouse = true;
+        self
+    }
+    /// Like `Select::alternate_screen`
+    pub fn alternate_screen(&mut self) -> &mut Self {
+        self.alternate_screen = true;
+        self
+    }
+    /// Like `Select::clear_on_exit`
+    pub fn clear_on_exit(&mut self) -> &mut Self {
+        self.clear_on_exit = true;
+        self
+    }
+    /// Like `Select::no_color`
+    pub fn no_color(&mut self) -> &mut Self {
+        self.no_color = true;
+        self
+    }
+    /// Like `Select::position_indicator`
+    pub fn position_indicator(&mut self) -> &mut Self {
+        self.position_indicator = true;
+        self
+    }
+    /// Like `Select::page_size`
+    pub fn page_size(&mut self, page_size: usize) -> &mut Self {
+        self.page_size = page_size;
+        self
+    }
+    /// Like `Select::visible_rows`
+    pub fn visible_rows(&mut self, rows: usize) -> &mut Self {
+        self.visible_rows = Some(rows);
+        self
+    }
+    /// Like `Select::max_visible`
+    pub fn max_visible(&mut self, max_visible: usize) -> &mut Self {
+        self.max_visible = Some(max_visible);
+        self
+    }
+    /// Like `Select::scroll_off`
+    pub fn scroll_off(&mut self, rows: usize) -> &mut Self {
+        self.scroll_off = rows;
+        self
+    }
+    /// Like `Select::multi_select`
+    pub fn multi_select(&mut self) -> &mut Self {
+        self.multi_select = true;
+        self
+    }
+    /// Like `Select::paginate`
+    pub fn paginate(&mut self, per_page: usize) -> &mut Self {
+        self.per_page = Some(per_page.max(1));
+        self
+    }
+    /// Like `Select::max_width`
+    pub fn max_width(&mut self, width: usize) -> &mut Self {
+        self.max_width = Some(width);
+        self
+    }
+    /// Like `Select::bordered`
+    pub fn bordered(&mut self) -> &mut Self {
+        self.bordered = true;
+        self
+    }
+    /// Like `Select::accessible`
+    pub fn accessible(&mut self) -> &mut Self {
+        self.accessible = true;
+        self
+    }
+    /// Like `Select::horizontal`
+    pub fn horizontal(&mut self) -> &mut Self {
+        self.horizontal = true;
+        self
+    }
+    /// Like `Select::confirm_twice`
+    pub fn confirm_twice(&mut self) -> &mut Self {
+        self.confirm_twice = true;
+        self
+    }
+    /// Like `Select::number_selects_immediately`. Enables `with_numbers`.
+    pub fn number_selects_immediately(&mut self) -> &mut Self {
+        self.with_numbers = true;
+        self.number_selects_immediately = true;
+        self
+    }
+    /// Like `Select::timeout`
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Like `Select::hard_timeout`
+    pub fn hard_timeout(&mut self) -> &mut Self {
+        self.hard_timeout = true;
+        self
+    }
+    /// Like `Select::timeout_cancels`
+    pub fn timeout_cancels(&mut self) -> &mut Self {
+        self.timeout_cancels = true;
+        self
+    }
+    /// Like `Select::throttle`
+    pub fn throttle(&mut self, duration: Duration) -> &mut Self {
+        self.throttle = Some(duration);
+        self
+    }
+    /// Like `Select::min_selections`
+    pub fn min_selections(&mut self, min: usize) -> &mut Self {
+        self.min_selections = min;
+        self
+    }
+    /// Like `Select::max_selections`
+    pub fn max_selections(&mut self, max: usize) -> &mut Self {
+        self.max_selections = Some(max);
+        self
+    }
+    /// Like `Select::editable`
+    pub fn editable(&mut self) -> &mut Self {
+        self.editable = true;
+        self
+    }
+    /// Like `Select::min_items`
+    pub fn min_items(&mut self, count: usize) -> &mut Self {
+        self.min_items = count.max(1);
+        self
+    }
+    /// Like `Select::headless`
+    pub fn headless(&mut self, index: usize) -> &mut Self {
+        self.headless = Some(index);
+        self
+    }
+    /// Like `Select::submit_on_single_item`
+    pub fn submit_on_single_item(&mut self) -> &mut Self {
+        self.submit_on_single_item = true;
+        self
+    }
+    /// Like `Select::report_selection`
+    pub fn report_selection(&mut self, prefix: impl Into<String>) -> &mut Self {
+        self.report_prefix = Some(prefix.into());
+        self
+    }
+    /// Like `Select::pointer`
+    pub fn pointer(&mut self, pointer: char) -> &mut Self {
+        self.pointer_str(pointer)
+    }
+    /// Like `Select::pointer_str`
+    pub fn pointer_str(&mut self, pointer: impl Into<String>) -> &mut Self {
+        self.theme.pointer = pointer.into();
+        self
+    }
+    /// Like `Select::not_selected_pointer`
+    pub fn not_selected_pointer(&mut self, pointer: char) -> &mut Self {
+        self.theme.not_selected_pointer = Some(pointer);
+        self
+    }
+    /// Like `Select::no_pointer`
+    pub fn no_pointer(&mut self) -> &mut Self {
+        self.theme.no_pointer = true;
+        self
+    }
+    /// Like `Select::pointer_color`
+    pub fn pointer_color(&mut self, color: Color) -> &mut Self {
+        self.theme.pointer_color = Some(color);
+        self
+    }
+    /// Like `Select::move_selected_item_forward`
+    pub fn move_selected_item_forward(&mut self) -> &mut Self {
+        self.indent_selected(1)
+    }
+    /// Like `Select::indent_selected`
+    pub fn indent_selected(&mut self, spaces: usize) -> &mut Self {
+        self.theme.selected_indent = spaces;
+        self
+    }
+    /// Like `Select::selected_fg`
+    pub fn selected_fg(&mut self, color: Color) -> &mut Self {
+        self.theme.selected_fg = Some(color);
+        self
+    }
+    /// Like `Select::selected_bg`
+    pub fn selected_bg(&mut self, color: Color) -> &mut Self {
+        self.theme.selected_bg = Some(color);
+        self
+    }
+    /// Like `Select::underline_selected_item`
+    pub fn underline_selected_item(&mut self) -> &mut Self {
+        self.theme.underline_selected_item = true;
+        self
+    }
+    /// Like `Select::reverse_selected_item`
+    pub fn reverse_selected_item(&mut self) -> &mut Self {
+        self.theme.reverse_selected_item = true;
+        self
+    }
+    /// Like `Select::bold_selected_item`
+    pub fn bold_selected_item(&mut self) -> &mut Self {
+        self.theme.bold_selected_item = true;
+        self
+    }
+    /// Like `Select::dim_unselected`
+    pub fn dim_unselected(&mut self) -> &mut Self {
+        self.theme.dim_unselected = true;
+        self
+    }
+    /// Like `Select::bold_title`
+    pub fn bold_title(&mut self) -> &mut Self {
+        self.theme.bold_title = true;
+        self
+    }
+    /// Like `Select::set_up_key`
+    pub fn set_up_key(&mut self, key: KeyCode) -> &mut Self {
+        replace_first_key(&mut self.key_map.up, key);
+        self
+    }
+    /// Like `Select::set_down_key`
+    pub fn set_down_key(&mut self, key: KeyCode) -> &mut Self {
+        replace_first_key(&mut self.key_map.down, key);
+        self
+    }
+    /// Like `Select::add_up_key`. Fails if the key is enter while it confirms the selection.
+    pub fn add_up_key(&mut self, key: KeyCode) -> Result<&mut Self, SelectError> {
+        self.add_up_key_with_modifiers(key, KeyModifiers::NONE)
+    }
+    /// Like `Select::add_down_key`. Fails if the key is enter while it confirms the selection.
+    pub fn add_down_key(&mut self, key: KeyCode) -> Result<&mut Self, SelectError> {
+        self.add_down_key_with_modifiers(key, KeyModifiers::NONE)
+    }
+    /// Like `Select::add_up_key_with_modifiers`
+    pub fn add_up_key_with_modifiers(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<&mut Self, SelectError> {
+        self.key_map.check_move_key(key)?;
+        self.key_map.up.push(KeyEvent::new(key, modifiers));
+        Ok(self)
+    }
+    /// Like `Select::add_down_key_with_modifiers`
+    pub fn add_down_key_with_modifiers(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<&mut Self, SelectError> {
+        self.key_map.check_move_key(key)?;
+        self.key_map.down.push(KeyEvent::new(key, modifiers));
+        Ok(self)
+    }
+    /// Like `Select::up_keys`. Fails without adding any key if one of them is enter while it confirms the selection.
+    pub fn up_keys(&mut self, keys: &[KeyCode]) -> Result<&mut Self, SelectError> {
+        for key in keys {
+            self.key_map.check_move_key(*key)?;
+        }
+        self.key_map.up.extend(
+            keys.iter()
+                .map(|key| KeyEvent::new(*key, KeyModifiers::NONE)),
+        );
+        Ok(self)
+    }
+    /// Like `Select::down_keys`. Fails without adding any key if one of them is enter while it confirms the selection.
+    pub fn down_keys(&mut self, keys: &[KeyCode]) -> Result<&mut Self, SelectError> {
+        for key in keys {
+            self.key_map.check_move_key(*key)?;
+        }
+        self.key_map.down.extend(
+            keys.iter()
+                .map(|key| KeyEvent::new(*key, KeyModifiers::NONE)),
+        );
+        Ok(self)
+    }
+    /// Like `Select::vim_keys`
+    pub fn vim_keys(&mut self) -> &mut Self {
+        self.key_map.add_vim_keys();
+        self
+    }
+    /// Like `Select::tab_navigation`
+    pub fn tab_navigation(&mut self) -> &mut Self {
+        self.key_map.add_tab_navigation();
+        self
+    }
+    /// Like `Select::confirm_key`
+    pub fn confirm_key(&mut self, key: KeyCode) -> &mut Self {
+        self.key_map.confirm = vec![KeyEvent::new(key, KeyModifiers::NONE)];
+        self
+    }
+    /// Like `Select::add_confirm_key`
+    pub fn add_confirm_key(&mut self, key: KeyCode) -> &mut Self {
+        self.key_map
+            .confirm
+            .push(KeyEvent::new(key, KeyModifiers::NONE));
+        self
+    }
+}
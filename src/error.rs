@@ -0,0 +1,74 @@
+use crossterm::event::KeyEvent;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::sync::Arc;
+
+/// Errors that can occur while configuring or running a select dialog
+///
+/// Errors compare equal if they are the same variant with the same key, I/O errors if they are of the same kind.
+#[derive(Clone, Debug)]
+pub enum SelectError {
+    /// The enter key is reserved to confirm the selection and can't be used to move up or down
+    EnterKeyNotSupported,
+    /// The dialog was started without any items to select from
+    EmptyList,
+    /// The dialog was started while the key is bound to more than one action, for example to up and to confirm
+    ConflictingKeyBinding(KeyEvent),
+    /// The dialog was started while the input is not a terminal, for example in a pipe, so the user can't select an item.
+    /// Use `Select::headless` to choose an item without a terminal instead
+    NotATty,
+    /// Reading the input of the user or writing the output failed, for example because the input was closed.
+    /// The error is shared, so `SelectError` can be cloned
+    Io(Arc<io::Error>),
+}
+
+impl Display for SelectError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SelectError::EnterKeyNotSupported => {
+                write!(f, "Enter key is not supported as up/down key")
+            }
+            SelectError::EmptyList => write!(f, "There are no items to select from"),
+            SelectError::ConflictingKeyBinding(key) => {
+                write!(f, "The key {:?} is bound to more than one action", key.code)
+            }
+            SelectError::NotATty => write!(f, "The input is not a terminal"),
+            SelectError::Io(error) => write!(
+                f,
+                "Reading the input or writing the output failed: {}",
+                error
+            ),
+        }
+    }
+}
+
+impl Error for SelectError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SelectError::Io(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for SelectError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SelectError::Io(error), SelectError::Io(other)) => error.kind() == other.kind(),
+            (
+                SelectError::ConflictingKeyBinding(key),
+                SelectError::ConflictingKeyBinding(other),
+            ) => key == other,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for SelectError {}
+
+impl From<io::Error> for SelectError {
+    fn from(error: io::Error) -> Self {
+        SelectError::Io(Arc::new(error))
+    }
+}
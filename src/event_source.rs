@@ -0,0 +1,89 @@
+use crossterm::event::{self, Event, KeyEvent};
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+/// The input of the select dialog. By default the events are read from the terminal.
+///
+/// Pass another source to `Select::event_source` to script the input, for example in tests.
+pub trait EventSource {
+    /// Returns whether an event can be read within the timeout
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool>;
+    /// Returns the next event and waits for it if there is none yet
+    fn read(&mut self) -> io::Result<Event>;
+}
+
+/// Reads the events of the terminal with crossterm
+pub(crate) struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
+    }
+    fn read(&mut self) -> io::Result<Event> {
+        event::read()
+    }
+}
+
+/// Returns the given events one after another, as if the user pressed the keys.
+/// `idle` lets a poll time out in between, as if the user waited, for example to test a timeout.
+///
+/// Once all events are read, reading fails with `io::ErrorKind::UnexpectedEof`, like a closed input.
+///
+/// ```
+/// use cli_select::{KeyCode, KeyEvent, KeyModifiers, Select, VecEventSource};
+///
+/// let items = vec!["item1", "item2", "item3"];
+/// let keys = [KeyCode::Down, KeyCode::Enter].map(|code| KeyEvent::new(code, KeyModifiers::NONE));
+///
+/// let mut select = Select::new(&items, Vec::new());
+/// select.event_source(VecEventSource::from_keys(keys));
+///
+/// assert_eq!(select.start(), Ok(Some(&"item2")));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct VecEventSource {
+    // None is a pause, in which polling times out
+    events: VecDeque<Option<Event>>,
+}
+
+impl VecEventSource {
+    /// Creates a source of the given events, for example key presses, mouse clicks and resizes
+    pub fn new(events: impl IntoIterator<Item = Event>) -> Self {
+        VecEventSource {
+            events: events.into_iter().map(Some).collect(),
+        }
+    }
+    /// Creates a source that presses the given keys
+    pub fn from_keys(keys: impl IntoIterator<Item = KeyEvent>) -> Self {
+        VecEventSource::new(keys.into_iter().map(Event::Key))
+    }
+    /// Adds a pause after the events so far: the next poll times out instead of returning an event
+    pub fn idle(mut self) -> Self {
+        self.events.push_back(None);
+        self
+    }
+}
+
+impl EventSource for VecEventSource {
+    fn poll(&mut self, _timeout: Duration) -> io::Result<bool> {
+        if let Some(None) = self.events.front() {
+            self.events.pop_front();
+            return Ok(false);
+        }
+        // without events, reading reports the closed input
+        Ok(true)
+    }
+    fn read(&mut self) -> io::Result<Event> {
+        // a read waits until the pauses are over
+        while let Some(event) = self.events.pop_front() {
+            if let Some(event) = event {
+                return Ok(event);
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "no more events",
+        ))
+    }
+}
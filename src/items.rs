@@ -0,0 +1,18 @@
+use std::ops::Deref;
+
+/// The items of a select dialog, either borrowed from the caller or owned by the dialog
+pub enum Items<'a, I> {
+    Borrowed(&'a [I]),
+    Owned(Vec<I>),
+}
+
+impl<'a, I> Deref for Items<'a, I> {
+    type Target = [I];
+
+    fn deref(&self) -> &[I] {
+        match self {
+            Items::Borrowed(items) => items,
+            Items::Owned(items) => items,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::SelectError;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The keys of all actions of the select dialog.
+///
+/// `KeyMap::default()` contains the keys the dialog uses without configuration. Change its fields to define
+/// a whole key scheme in one place and pass it to `Select::key_map`.
+///
+/// ```
+/// use cli_select::{KeyCode, KeyEvent, KeyMap, KeyModifiers};
+///
+/// let mut key_map = KeyMap::default();
+/// key_map.up.push(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
+/// key_map.down.push(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyMap {
+    /// Move the selection up. Letters match regardless of their case. Default is the up arrow
+    pub up: Vec<KeyEvent>,
+    /// Move the selection down. Letters match regardless of their case. Default is the down arrow
+    pub down: Vec<KeyEvent>,
+    /// Move the selection up by the page size. Default is page up
+    pub page_up: Vec<KeyEvent>,
+    /// Move the selection down by the page size. Default is page down
+    pub page_down: Vec<KeyEvent>,
+    /// Move the selection up by half of the visible rows. No key by default, `Select::vim_keys` adds Ctrl+U
+    pub half_page_up: Vec<KeyEvent>,
+    /// Move the selection down by half of the visible rows. No key by default, `Select::vim_keys` adds Ctrl+D
+    pub half_page_down: Vec<KeyEvent>,
+    /// Highlight the first item. Letters only match in the same case. Default is home
+    pub home: Vec<KeyEvent>,
+    /// Highlight the last item. Letters only match in the same case. Default is end
+    pub end: Vec<KeyEvent>,
+    /// Choose the highlighted item. Default is enter
+    pub confirm: Vec<KeyEvent>,
+    /// Stop the dialog without a chosen item. Default is escape and Ctrl+C
+    pub cancel: Vec<KeyEvent>,
+    /// Remove the highlighted item if the dialog is editable. Default is delete and backspace.
+    /// Backspace edits the filter instead if the dialog is filterable.
+    /// The keys only count as bound, and may conflict with other actions, if the dialog is editable
+    pub remove: Vec<KeyEvent>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap {
+            up: vec![key(KeyCode::Up)],
+            down: vec![key(KeyCode::Down)],
+            page_up: vec![key(KeyCode::PageUp)],
+            page_down: vec![key(KeyCode::PageDown)],
+            half_page_up: vec![],
+            half_page_down: vec![],
+            home: vec![key(KeyCode::Home)],
+            end: vec![key(KeyCode::End)],
+            confirm: vec![key(KeyCode::Enter)],
+            cancel: vec![
+                key(KeyCode::Esc),
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ],
+            remove: vec![key(KeyCode::Delete), key(KeyCode::Backspace)],
+        }
+    }
+}
+
+impl KeyMap {
+    /// Enter can only be used to move up or down if it is no confirm key
+    pub(crate) fn check_move_key(&self, key: KeyCode) -> Result<(), SelectError> {
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        if key == KeyCode::Enter && self.confirm.contains(&enter) {
+            return Err(SelectError::EnterKeyNotSupported);
+        }
+        Ok(())
+    }
+    /// Adds j and k, g and G, Ctrl+D and Ctrl+U
+    pub(crate) fn add_vim_keys(&mut self) {
+        self.down.push(key(KeyCode::Char('j')));
+        self.up.push(key(KeyCode::Char('k')));
+        self.home.push(key(KeyCode::Char('g')));
+        self.end
+            .push(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT));
+        self.half_page_up
+            .push(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        self.half_page_down
+            .push(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL));
+    }
+    /// Adds tab to move down and Shift+Tab to move up
+    pub(crate) fn add_tab_navigation(&mut self) {
+        self.down.push(key(KeyCode::Tab));
+        self.up.push(key(KeyCode::BackTab));
+    }
+    /// Enter can only move the selection if it doesn't confirm it
+    pub(crate) fn check(&self) -> Result<(), SelectError> {
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        if self.confirm.contains(&enter) && (self.up.contains(&enter) || self.down.contains(&enter))
+        {
+            return Err(SelectError::EnterKeyNotSupported);
+        }
+        Ok(())
+    }
+    /// Returns the first key that is bound to more than one action.
+    /// Letters of up, down and confirm conflict regardless of their case, because they match that way.
+    /// The remove keys are only checked if the dialog is editable.
+    pub(crate) fn conflicting_key(&self, editable: bool) -> Option<KeyEvent> {
+        let no_keys = vec![];
+        let remove = if editable { &self.remove } else { &no_keys };
+        let actions = [
+            (&self.up, true),
+            (&self.down, true),
+            (&self.page_up, false),
+            (&self.page_down, false),
+            (&self.half_page_up, false),
+            (&self.half_page_down, false),
+            (&self.home, false),
+            (&self.end, false),
+            (&self.confirm, true),
+            (&self.cancel, false),
+            (remove, false),
+        ];
+        actions
+            .iter()
+            .enumerate()
+            .find_map(|(action, (keys, ignore_case))| {
+                keys.iter()
+                    .find(|key| {
+                        actions[action + 1..]
+                            .iter()
+                            .any(|(others, other_ignores_case)| {
+                                others.iter().any(|other| {
+                                    same_press(key, other, *ignore_case || *other_ignores_case)
+                                })
+                            })
+                    })
+                    .copied()
+            })
+    }
+}
+
+/// Returns whether both keys react to the same press. Letters are compared regardless of their case if ignore_case is set
+fn same_press(key: &KeyEvent, other: &KeyEvent, ignore_case: bool) -> bool {
+    match (key.code, other.code) {
+        (KeyCode::Char(c), KeyCode::Char(other_c)) if ignore_case => {
+            key.modifiers == other.modifiers && c.eq_ignore_ascii_case(&other_c)
+        }
+        _ => key == other,
+    }
+}
+
+/// Replaces the first of the keys, or adds the key if there is none
+pub(crate) fn replace_first_key(keys: &mut Vec<KeyEvent>, code: KeyCode) {
+    match keys.first_mut() {
+        Some(first) => *first = key(code),
+        None => keys.push(key(code)),
+    }
+}
+
+/// Returns the press of the key without modifiers
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
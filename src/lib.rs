@@ -1,22 +1,105 @@
+// The tests of every module are kept right after its imports
+#![allow(clippy::items_after_test_module)]
+
+mod builder;
+mod error;
+mod event_source;
+mod items;
+mod key_map;
 mod line;
 mod select;
+mod terminal;
+mod theme;
+#[cfg(feature = "async")]
+mod ticks;
 
-pub use crossterm::event::KeyCode;
+pub use builder::SelectBuilder;
+pub use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+pub use crossterm::style::Color;
+pub use error::SelectError;
+pub use event_source::{EventSource, VecEventSource};
+pub use key_map::KeyMap;
+pub use line::Line;
 pub use select::Select;
+pub use theme::Theme;
+
 #[cfg(test)]
 mod tests {
     #[test]
     fn it_works() {}
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SelectDialogKey {
+    /// The dialog was printed the first time and no key was pressed yet
+    Initial,
     UpKey,
     DownKey,
+    PageUpKey,
+    PageDownKey,
+    /// The selection moved up by half of the visible rows
+    HalfPageUpKey,
+    /// The selection moved down by half of the visible rows
+    HalfPageDownKey,
+    HomeKey,
+    EndKey,
+    /// The number of an item was pressed
+    NumberKey,
+    /// An item was clicked with the mouse
+    MouseClick,
+    /// A letter was typed and the next item starting with it was highlighted
+    TypeAhead,
+    /// The highlighted item was removed in editable mode, the handler gets the item that is highlighted now
+    DeleteKey,
+    /// The dialog was confirmed with the item. The returned flow control has no effect
+    Confirm,
 }
 
 pub type SelectionChange<T> = Box<dyn Fn(SelectDialogKey, &T)>;
 
+/// Describes a change of the selection for the handler set with `Select::on_change`
+#[derive(Debug)]
+pub struct ChangeEvent<'a, T> {
+    /// The key that changed the selection
+    pub key: SelectDialogKey,
+    /// The index of the item that was selected when the handler was called before. None the first time
+    pub old_index: Option<usize>,
+    /// The index of the selected item
+    pub new_index: usize,
+    /// The selected item
+    pub item: &'a T,
+}
+
+/// The list after it was edited with `Select::start_editable`
+#[derive(Debug, Eq, PartialEq)]
+pub struct EditedList<'a, T> {
+    /// The items that were not removed, in the order of the item list
+    pub remaining: Vec<&'a T>,
+    /// The chosen item. None if the dialog was cancelled
+    pub chosen: Option<&'a T>,
+}
+
+pub type ChangeHandler<T> = Box<dyn Fn(&ChangeEvent<'_, T>)>;
+
+/// Tells the select dialog how to continue after a handler was called
+#[derive(Debug, Eq, PartialEq)]
+pub enum FlowControl {
+    /// Keep waiting for the users input
+    Continue,
+    /// Choose the current item and stop the dialog
+    Confirm,
+    /// Stop the dialog without a chosen item
+    Cancel,
+}
+
+pub type SelectionFlow<T> = Box<dyn Fn(SelectDialogKey, &T) -> FlowControl>;
+
+pub type ItemRenderer<'a, T> = Box<dyn Fn(&T, bool) -> String + 'a>;
+
+pub type KeyHandler = Box<dyn Fn(KeyEvent) -> FlowControl>;
+
+pub type ItemLabel<'a, T> = Box<dyn Fn(&T) -> String + 'a>;
+
 // pub fn test() {
 //     let vec = select!("xxx", "abc");
 // }
@@ -1,9 +1,14 @@
 use core::fmt;
+use crossterm::style::{
+    Attribute, Color, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 #[cfg(test)]
 mod tests {
-    use super::Line;
+    use super::{Color, Line};
 
     #[test]
     fn selected_line_printed_with_pointer() {
@@ -16,15 +21,221 @@ mod tests {
         let line = Line::new(String::from("test"), '>');
         assert_eq!(line.to_string(), "  test")
     }
+    #[test]
+    fn unselected_line_printed_with_not_selected_pointer() {
+        let mut line = Line::new(String::from("test"), '>');
+        line.not_selected_pointer('○');
+        line.select();
+        line.default();
+        assert_eq!(line.to_string(), "○ test")
+    }
+    #[test]
+    fn line_printed_with_checkbox() {
+        let mut line = Line::new(String::from("test"), '>');
+        line.checkbox(true);
+        assert_eq!(line.to_string(), "  [x] test");
+        line.checkbox(false);
+        assert_eq!(line.to_string(), "  [ ] test");
+    }
+    #[test]
+    fn colored_line_resets_color_at_end() {
+        let mut line = Line::new(String::from("test"), '>');
+        line.foreground(Color::Red);
+        assert_eq!(line.to_string(), "\x1b[38;5;9m  test\x1b[0m");
+        line.default();
+        assert_eq!(line.to_string(), "  test");
+    }
+    #[test]
+    fn long_text_is_cut_to_max_width() {
+        let mut line = Line::new(String::from("a long item"), '>');
+        line.max_width(8);
+        assert_eq!(line.to_string(), "  a lon…");
+        assert_eq!(line.len(), 8);
+    }
+    #[test]
+    fn wide_characters_are_cut_by_cells() {
+        let mut line = Line::new(String::from("日本語です"), '>');
+        line.max_width(8);
+        assert_eq!(line.to_string(), "  日本…");
+    }
+    #[test]
+    fn len_counts_cells_of_wide_characters() {
+        let line = Line::new(String::from("日本語"), '>');
+        assert_eq!(line.len(), 8);
+    }
+
+    #[test]
+    fn bold_and_underline_can_be_combined_with_colors() {
+        let mut line = Line::new(String::from("test"), '>');
+        line.bold();
+        line.underline();
+        line.foreground(Color::Red);
+        assert_eq!(
+            line.to_string(),
+            "\x1b[38;5;9m  \x1b[1m\x1b[4mtest\x1b[24m\x1b[22m\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn pointer_color_applies_to_pointer_only() {
+        let mut line = Line::new(String::from("test"), '>');
+        line.pointer_color(Color::Green);
+        assert_eq!(line.to_string(), "  test");
+
+        line.select();
+        assert_eq!(line.to_string(), "\x1b[38;5;10m>\x1b[39m test");
+        assert_eq!(line.len(), 6);
+    }
+
+    #[test]
+    fn hidden_pointer_starts_text_at_first_column() {
+        let mut line = Line::new(String::from("test"), '>');
+        line.hide_pointer();
+        line.select();
+        assert_eq!(line.to_string(), "test");
+        assert_eq!(line.len(), 4);
+
+        line.space_from_pointer(2);
+        assert_eq!(line.to_string(), " test");
+    }
+
+    #[test]
+    fn reverse_is_turned_off_at_line_end() {
+        let mut line = Line::new(String::from("test"), '>');
+        line.select();
+        line.reverse();
+        assert_eq!(line.to_string(), "\x1b[7m> test\x1b[27m");
+        assert_eq!(line.len(), 6);
+    }
+
+    #[test]
+    fn annotation_aligned_to_max_width() {
+        let mut line = Line::new(String::from("a long item"), '>');
+        line.annotation(String::from("^O"));
+        line.max_width(20);
+        assert_eq!(line.to_string(), "  a long item     \x1b[2m^O\x1b[22m");
+        assert_eq!(line.len(), 20);
+
+        line.max_width(10);
+        assert_eq!(line.to_string(), "  a lo… \x1b[2m^O\x1b[22m");
+        assert_eq!(line.len(), 10);
+    }
+    #[test]
+    fn pointer_of_several_characters_counts_its_width() {
+        let mut line = Line::new(String::from("item"), '>');
+        line.pointer("=>");
+        line.select();
+
+        assert_eq!(line.to_string(), "=> item");
+        assert_eq!(line.len(), 7);
+    }
+    #[test]
+    fn number_is_aligned_to_number_width() {
+        let mut line = Line::new(String::from("item"), '>');
+        line.number(9);
+        line.number_width(2);
+
+        assert_eq!(line.to_string(), "   9. item");
+        assert_eq!(line.len(), 10);
+    }
+    #[test]
+    fn escape_codes_in_text_take_no_width() {
+        let mut colored = Line::new(String::from("\x1b[31merror\x1b[0m"), '>');
+        let mut plain = Line::new(String::from("error"), '>');
+        colored.annotation(String::from("E1"));
+        plain.annotation(String::from("E1"));
+        colored.max_width(12);
+        plain.max_width(12);
+
+        assert_eq!(colored.len(), plain.len());
+        assert_eq!(
+            colored.to_string(),
+            plain.to_string().replace("error", "\x1b[31merror\x1b[0m")
+        );
+    }
+    #[test]
+    fn cut_text_keeps_escape_codes() {
+        let mut line = Line::new(String::from("\x1b[31ma long error\x1b[0m"), '>');
+        line.max_width(7);
+
+        assert_eq!(line.to_string(), "  \x1b[31ma lo…\x1b[0m");
+        assert_eq!(line.len(), 7);
+    }
+    #[test]
+    fn not_selected_line_reserves_width_of_pointer() {
+        let mut line = Line::new(String::from("item"), '>');
+        line.pointer("=>");
+
+        assert_eq!(line.to_string(), "   item");
+        assert_eq!(line.len(), 7);
+
+        line.not_selected_pointer('-');
+        assert_eq!(line.to_string(), "-  item");
+
+        line.select();
+        assert_eq!(line.to_string(), "=> item");
+
+        line.default();
+        assert_eq!(line.to_string(), "-  item");
+    }
+    #[test]
+    fn dimmed_line_is_reset_by_default() {
+        let mut line = Line::new(String::from("item"), '>');
+        line.dim();
+
+        assert_eq!(line.to_string(), "\x1b[2m  item\x1b[22m");
+        assert_eq!(line.len(), 6);
+
+        line.default();
+        assert_eq!(line.to_string(), "  item");
+    }
+    #[test]
+    fn disabled_line_keeps_its_colors() {
+        let mut line = Line::new(String::from("item"), '>');
+        line.disable();
+        line.foreground(Color::Red);
+
+        assert_eq!(line.to_string(), "\x1b[38;5;9m\x1b[2m  item\x1b[22m\x1b[0m");
+    }
 }
+
+/// A single printed row of the select dialog: pointer, optional checkbox and number, and the text.
+///
+/// The line is rendered with `Display`. The output is a single row without a line break,
+/// that may contain escape codes for its styles. `len` returns the number of terminal cells it takes,
+/// which is needed to overwrite it later.
+///
+/// ```
+/// use cli_select::Line;
+///
+/// let mut line = Line::new(String::from("item"), '>');
+/// line.select();
+/// assert_eq!(line.to_string(), "> item");
+///
+/// line.default();
+/// assert_eq!(line.to_string(), "  item");
+/// ```
 #[derive(Debug)]
 pub struct Line {
     text: String,
     is_selected: bool,
-    pointer: char,
+    pointer: String,
     not_selected_pointer: char,
+    pointer_hidden: bool,
     space: usize,
     underline: bool,
+    bold: bool,
+    reverse: bool,
+    checkbox: Option<bool>,
+    foreground: Option<Color>,
+    background: Option<Color>,
+    pointer_color: Option<Color>,
+    max_width: Option<usize>,
+    number: Option<usize>,
+    number_width: usize,
+    annotation: Option<String>,
+    disabled: bool,
+    dim: bool,
 }
 
 impl Line {
@@ -33,62 +244,381 @@ impl Line {
         Line {
             text,
             is_selected: false,
-            pointer,
+            pointer: pointer.to_string(),
             space: 1,
             underline: false,
+            bold: false,
+            reverse: false,
             not_selected_pointer: ' ',
+            pointer_hidden: false,
+            checkbox: None,
+            foreground: None,
+            background: None,
+            pointer_color: None,
+            max_width: None,
+            number: None,
+            number_width: 0,
+            annotation: None,
+            disabled: false,
+            dim: false,
         }
     }
+    /// Returns the text of the line without pointer and styles
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// Replace the text of the line
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
     /// Show the pointer for this line
     pub fn select(&mut self) {
         self.is_selected = true;
     }
+    /// Replace the pointer of the selected line, which may be longer than one character, for example `=>`
+    pub fn pointer(&mut self, pointer: impl Into<String>) {
+        self.pointer = pointer.into();
+    }
+    /// Show the given pointer instead of a blank space while the line is not selected.
+    /// Either pointer is padded with spaces to the width of the wider one, so the texts stay aligned.
     pub fn not_selected_pointer(&mut self, pointer: char) {
         self.not_selected_pointer = pointer;
     }
+    /// Don't print a pointer, so the text starts at the beginning of the row.
+    /// The selection has to be shown with styles instead. The space from the pointer is reduced by one.
+    pub fn hide_pointer(&mut self) {
+        self.pointer_hidden = true;
+    }
+    /// Underline the text, the pointer is not underlined
     pub fn underline(&mut self) {
         self.underline = true;
     }
+    /// Swap text and background color of the whole line, including the pointer
+    pub fn reverse(&mut self) {
+        self.reverse = true;
+    }
+    /// Print the text in bold, the pointer is not bold
+    pub fn bold(&mut self) {
+        self.bold = true;
+    }
+    /// Print the whole line in the given text color
+    pub fn foreground(&mut self, color: Color) {
+        self.foreground = Some(color);
+    }
+    /// Print the whole line on the given background color
+    pub fn background(&mut self, color: Color) {
+        self.background = Some(color);
+    }
+    /// Print the pointer of the selected line in the given color, independent of the color of the text
+    pub fn pointer_color(&mut self, color: Color) {
+        self.pointer_color = Some(color);
+    }
+    /// Show a checkbox in front of the text that is either checked or unchecked
+    pub fn checkbox(&mut self, checked: bool) {
+        self.checkbox = Some(checked);
+    }
+    /// Limit the number of terminal cells the line can take. Longer text is cut and ends with an ellipsis.
+    pub fn max_width(&mut self, max_width: usize) {
+        self.max_width = Some(max_width);
+    }
+    /// Print the line dimmed to show that it can't be selected
+    pub fn disable(&mut self) {
+        self.disabled = true;
+    }
+    /// Print the whole line dimmed, for example to make the selected line stand out
+    pub fn dim(&mut self) {
+        self.dim = true;
+    }
+    /// Show a dimmed secondary text at the end of the line, for example a shortcut.
+    /// With a max width, it is aligned to the right and the text is cut first if space is tight.
+    pub fn annotation(&mut self, annotation: String) {
+        self.annotation = Some(annotation);
+    }
+    /// Show the given number in front of the text
+    pub fn number(&mut self, number: usize) {
+        self.number = Some(number);
+    }
+    /// Align the number to the right within the given count of digits,
+    /// so the texts of all lines start in the same column, for example ` 9. ` above `10. `
+    pub fn number_width(&mut self, digits: usize) {
+        self.number_width = digits;
+    }
     /// Define the space between pointer and item. Default is 1.
     pub fn space_from_pointer(&mut self, space: usize) {
         self.space = space;
     }
-    /// set all changes back to default that were made after creation
+    /// Set the selection and the styles back to default: not selected, one space after the pointer,
+    /// no underline, bold, reverse or dim, no checkbox and no colors. Text, pointers, max width and number are kept.
     pub fn default(&mut self) {
         self.is_selected = false;
         self.space = 1;
         self.underline = false;
+        self.bold = false;
+        self.reverse = false;
+        self.dim = false;
+        self.checkbox = None;
+        self.foreground = None;
+        self.background = None;
+        self.pointer_color = None;
     }
-    /// ascii code to underline
-    fn underline_text(&self, text: &str) -> String {
-        format!("[4m{}[0m", text)
+    /// Wraps the text in the escape codes of its attributes.
+    /// Every attribute is turned off on its own, so they can be combined with each other and with colors.
+    fn styled_text<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if !self.underline && !self.bold {
+            return Cow::Borrowed(text);
+        }
+        let mut styled = String::new();
+
+        if self.bold {
+            styled.push_str(&SetAttribute(Attribute::Bold).to_string());
+        }
+        if self.underline {
+            styled.push_str(&SetAttribute(Attribute::Underlined).to_string());
+        }
+        styled.push_str(text);
+
+        if self.underline {
+            styled.push_str(&SetAttribute(Attribute::NoUnderline).to_string());
+        }
+        if self.bold {
+            styled.push_str(&SetAttribute(Attribute::NormalIntensity).to_string());
+        }
+        Cow::Owned(styled)
+    }
+    fn checkbox_text(&self) -> &str {
+        match self.checkbox {
+            Some(true) => "[x] ",
+            Some(false) => "[ ] ",
+            None => "",
+        }
+    }
+    fn displayed_pointer(&self) -> Cow<'_, str> {
+        if self.is_selected {
+            Cow::Borrowed(&self.pointer)
+        } else {
+            Cow::Owned(self.not_selected_pointer.to_string())
+        }
+    }
+    /// Number of cells of the pointer column. Both pointers are padded to it, so the texts of
+    /// selected and not selected lines start in the same column, also behind a pointer like `=>`.
+    fn pointer_column_width(&self) -> usize {
+        self.pointer
+            .width()
+            .max(self.not_selected_pointer.width().unwrap_or(0))
+    }
+    /// Returns the spaces that fill the pointer column behind the displayed pointer
+    fn pointer_padding(&self) -> String {
+        " ".repeat(self.pointer_column_width() - self.displayed_pointer().width())
     }
+    /// Returns the pointer together with the escape codes of its own color
+    fn pointer_text(&self) -> String {
+        if self.pointer_hidden {
+            return String::new();
+        }
+        match (self.pointer_color, self.is_selected) {
+            (Some(color), true) => format!(
+                "{}{}{}{}",
+                SetForegroundColor(color),
+                self.pointer,
+                // the text continues in the color of the line
+                SetForegroundColor(self.foreground.unwrap_or(Color::Reset)),
+                self.pointer_padding()
+            ),
+            _ => format!("{}{}", self.displayed_pointer(), self.pointer_padding()),
+        }
+    }
+    fn number_text(&self) -> String {
+        match self.number {
+            Some(number) => format!("{:>width$}. ", number, width = self.number_width),
+            None => String::new(),
+        }
+    }
+    fn pointer_width(&self) -> usize {
+        if self.pointer_hidden {
+            0
+        } else {
+            self.pointer_column_width()
+        }
+    }
+    /// Number of spaces between pointer and text
+    fn spaces(&self) -> usize {
+        if self.pointer_hidden {
+            self.space.saturating_sub(1)
+        } else {
+            self.space
+        }
+    }
+    /// Number of cells in front of the text
+    fn prefix_len(&self) -> usize {
+        self.pointer_width() + self.spaces() + self.checkbox_text().len() + self.number_text().len()
+    }
+    /// Number of cells the annotation needs, including at least one space in front of it
+    fn annotation_len(&self) -> usize {
+        self.annotation
+            .as_ref()
+            .map_or(0, |annotation| annotation.width() + 1)
+    }
+    /// Number of spaces between text and annotation, so that the annotation ends at max_width
+    fn annotation_padding(&self, text_width: usize) -> usize {
+        match (&self.annotation, self.max_width) {
+            (None, _) => 0,
+            (Some(_), Some(max_width)) => {
+                max_width.saturating_sub(self.prefix_len() + text_width + self.annotation_len()) + 1
+            }
+            (Some(_), None) => 1,
+        }
+    }
+    /// Returns the text cut to fit into max_width, measured in terminal cells
+    fn fitted_text(&self) -> Cow<'_, str> {
+        let available = match self.max_width {
+            Some(max_width) => max_width.saturating_sub(self.prefix_len() + self.annotation_len()),
+            None => return Cow::Borrowed(&self.text),
+        };
+        if visible_width(&self.text) <= available {
+            return Cow::Borrowed(&self.text);
+        }
+        let mut text = String::new();
+        let mut width = 0;
+        let mut is_cut = false;
+        let mut rest = self.text.as_str();
+
+        while let Some(c) = rest.chars().next() {
+            if let Some(len) = escape_len(rest) {
+                // escape codes after the cut are kept, so colors of the text are still reset
+                text.push_str(&rest[..len]);
+                rest = &rest[len..];
+                continue;
+            }
+            let char_width = c.width().unwrap_or(0);
+
+            // one cell is needed for the ellipsis
+            if !is_cut && width + char_width + 1 > available {
+                text.push('…');
+                is_cut = true;
+            }
+            if !is_cut {
+                width += char_width;
+                text.push(c);
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        Cow::Owned(text)
+    }
+    /// Number of terminal cells the printed line takes
     pub fn len(&self) -> usize {
-        self.text.chars().count() + self.space + 1
+        let text_width = visible_width(&self.fitted_text());
+        let annotation_width = self
+            .annotation
+            .as_ref()
+            .map_or(0, |annotation| annotation.width());
+
+        self.prefix_len() + text_width + self.annotation_padding(text_width) + annotation_width
+    }
+    /// Returns true if the printed line takes no terminal cells
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
 impl Display for Line {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let text = if self.underline {
-            Some(self.underline_text(&self.text))
-        } else {
-            None
-        };
-        let pointer = if self.is_selected {
-            self.pointer
-        } else {
-            self.not_selected_pointer
+        let fitted_text = self.fitted_text();
+        let text = self.styled_text(&fitted_text);
+        let annotation = match &self.annotation {
+            Some(annotation) => format!(
+                "{}{}{}{}",
+                " ".repeat(self.annotation_padding(visible_width(&fitted_text))),
+                SetAttribute(Attribute::Dim),
+                annotation,
+                SetAttribute(Attribute::NormalIntensity)
+            ),
+            None => String::new(),
         };
-
         let result = format!(
-            "{}{}{}",
-            pointer,
-            " ".repeat(self.space),
-            text.as_ref().unwrap_or(&self.text),
+            "{}{}{}{}{}{}",
+            self.pointer_text(),
+            " ".repeat(self.spaces()),
+            self.checkbox_text(),
+            self.number_text(),
+            text,
+            annotation,
         );
 
-        write!(f, "{}", result)?;
+        if let Some(color) = self.foreground {
+            write!(f, "{}", SetForegroundColor(color))?;
+        }
+        if let Some(color) = self.background {
+            write!(f, "{}", SetBackgroundColor(color))?;
+        }
+        if self.reverse {
+            write!(f, "{}", SetAttribute(Attribute::Reverse))?;
+        }
+        if self.disabled || self.dim {
+            write!(
+                f,
+                "{}{}{}",
+                SetAttribute(Attribute::Dim),
+                result,
+                SetAttribute(Attribute::NormalIntensity)
+            )?;
+        } else {
+            write!(f, "{}", result)?;
+        }
+        if self.reverse {
+            write!(f, "{}", SetAttribute(Attribute::NoReverse))?;
+        }
+
+        if self.foreground.is_some() || self.background.is_some() {
+            write!(f, "{}", ResetColor)?;
+        }
         Ok(())
     }
 }
+
+/// Returns the number of terminal cells the text takes. Escape codes in the text, for example colors, take none.
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(len) = escape_len(rest) {
+            rest = &rest[len..];
+            continue;
+        }
+        width += c.width().unwrap_or(0);
+        rest = &rest[c.len_utf8()..];
+    }
+    width
+}
+
+/// Returns the length in bytes of the escape code the text starts with, if it starts with one
+fn escape_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+
+    if bytes.first() != Some(&0x1b) {
+        return None;
+    }
+    let len = match bytes.get(1) {
+        // control sequences like colors end with a byte from @ to ~
+        Some(b'[') => bytes[2..]
+            .iter()
+            .position(|byte| (0x40..=0x7e).contains(byte))
+            .map(|end| end + 3),
+        // operating system commands like links end with BEL or ESC \
+        Some(b']') => bytes[2..]
+            .windows(2)
+            .position(|pair| pair[0] == 0x07 || pair == b"\x1b\\")
+            .map(|end| {
+                if bytes[end + 2] == 0x07 {
+                    end + 3
+                } else {
+                    end + 4
+                }
+            })
+            .or_else(|| (bytes.last() == Some(&0x07)).then_some(bytes.len())),
+        Some(_) => Some(2),
+        None => Some(1),
+    };
+    // an escape code that isn't terminated takes the rest of the text
+    Some(len.unwrap_or(bytes.len()))
+}
@@ -1,12 +1,41 @@
-use crate::{line::Line, SelectDialogKey};
+use crate::{
+    builder::SelectBuilder,
+    error::SelectError,
+    event_source::{EventSource, TerminalEvents},
+    items::Items,
+    key_map::KeyMap,
+    line::Line,
+    terminal::{RawMode, TerminalModes},
+    theme::Theme,
+    ChangeEvent, ChangeHandler, EditedList, FlowControl, ItemLabel, ItemRenderer, KeyHandler,
+    SelectDialogKey, SelectionChange, SelectionFlow,
+};
 
+use crossterm::cursor::{self, MoveToNextLine, MoveToPreviousLine};
 use crossterm::event::{
-    read, Event, KeyCode,
-    KeyCode::{Down, Up},
-    KeyEvent, KeyModifiers,
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::execute;
+use crossterm::style::{Attribute, Color, SetAttribute};
+use crossterm::terminal::size;
+use crossterm::tty::IsTty;
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    io::{stdin, Write},
+    ops::Range,
+    time::{Duration, Instant},
 };
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
-use std::{fmt::Display, io::Write};
+use unicode_width::UnicodeWidthStr;
+
+#[cfg(feature = "async")]
+use crate::ticks::Ticks;
+#[cfg(feature = "async")]
+use crossterm::event::EventStream;
+#[cfg(feature = "async")]
+use futures::{future::Either, StreamExt};
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
 
 #[cfg(test)]
 mod tests {
@@ -20,10 +49,10 @@ mod tests {
         let mut select = Select::new(&items, buffer);
 
         Select::build_lines(&mut select);
-        Select::print_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
 
         assert_eq!(
-            "> item1\n  item2\n  item3\n",
+            "> item1\r\n  item2\r\n  item3\r\n",
             String::from_utf8(select.out).unwrap()
         )
     }
@@ -36,10 +65,10 @@ mod tests {
         let mut select = Select::new(&items, buffer);
 
         Select::build_lines(&mut select);
-        Select::move_down(&mut select);
+        Select::move_down(&mut select).unwrap();
 
         assert_eq!(
-            "  item1\n> item2\n  item3\n",
+            "  item1\r\n> item2\r\n  item3\r\n",
             String::from_utf8(select.out).unwrap()
         );
         assert_eq!(select.selected_item, 1);
@@ -53,7 +82,7 @@ mod tests {
         let mut select = Select::new(&items, buffer);
 
         Select::build_lines(&mut select);
-        Select::move_up(&mut select);
+        Select::move_up(&mut select).unwrap();
 
         assert_eq!("", String::from_utf8(select.out).unwrap());
         assert_eq!(select.selected_item, 0);
@@ -69,247 +98,4143 @@ mod tests {
         Select::build_lines(&mut select);
 
         select.selected_item = 2; // selected item is now item3
-        Select::move_down(&mut select);
+        Select::move_down(&mut select).unwrap();
 
         assert_eq!("", String::from_utf8(select.out).unwrap());
 
         assert_eq!(select.selected_item, 2);
     }
-}
 
-/// Struct to create a select dialog and get the users chosen item
-///
-/// The input is retrieved over an endless loop. When the user presses enter,
-/// the loop stops and the chosen item is returned.
-///
-/// # Example
-///
-/// Create the dialog with default settings
-///
-/// ```
-/// use cli_select::Select;
-/// use std::io::stdout;
-///
-/// let items = vec!["item1", "item2", "item3"];
-/// let selected_item = Select::new(&items, stdout()).start();
-/// ```
-///
-/// Customize dialog before starting
-///
-/// ```
-/// use cli_select::{Select, KeyCode};
-/// use std::io::stdout;
-///
-/// let items = vec!["item1", "item2", "item3"];
-/// let selected_item = Select::new(&items, stdout())
-///     .add_up_key(KeyCode::Char('j'))
-///     .pointer('◉')
-///     .not_selected_pointer('𐩒')
-///     .underline_selected_item()
-///     .start();
-/// ```
-pub struct Select<'a, I, W>
-where
-    I: ToString + Display,
-    W: Write, // W: std::io::Write, // F: Fn(SelectDialogKey, &I),
-{
-    items: &'a [I],
-    lines: Vec<Line>,
-    selected_item: usize,
-    pointer: char,
-    not_selected_pointer: Option<char>,
-    default_up: KeyCode,
-    default_down: KeyCode,
-    up_keys: Vec<KeyCode>,
-    down_keys: Vec<KeyCode>,
-    pub selection_changed: Option<Box<dyn Fn(SelectDialogKey, &I)>>,
-    move_selected_item_forward: bool,
-    underline_selected_item: bool,
-    longest_item_len: usize,
-    item_count: usize,
-    out: W,
-    // out: Option<W>, // logger: Logger<W>,
-}
+    /// test that the cursor is moved back by the height of the list, whatever its length
+    #[test]
+    fn erase_height_matches_item_count() {
+        let items = vec!["1", "2", "3", "4", "5", "6", "7"];
+        let buffer: Vec<u8> = vec![];
 
-impl<'a, I, W> Select<'a, I, W>
-where
-    I: ToString + Display + core::fmt::Debug,
-    W: std::io::Write,
-{
-    /// Create a new Select Dialog with lines defined in the items parameter.
-    ///
-    /// Any Struct that implements std::io::write can be used as output. Use std::io::stdout() as second parameter to print to console
-    pub fn new(items: &'a [I], out: W) -> Select<'a, I, W> {
-        Select {
-            items,
-            pointer: '>',
-            selected_item: 0,
-            default_up: Up,
-            default_down: Down,
-            selection_changed: None,
-            not_selected_pointer: None,
-            move_selected_item_forward: false,
-            underline_selected_item: false,
-            up_keys: vec![],
-            down_keys: vec![],
-            lines: vec![],
-            longest_item_len: 0,
-            item_count: 0,
-            out,
-        }
-    }
-    /// Builds the lines and store them for later usage. item_count and longest_item_len is initialized.
-    fn build_lines(&mut self) {
-        let mut lines: Vec<Line> = vec![];
-        let mut item_count: usize = 0;
-        for item in self.items {
-            let mut line = Line::new(item.to_string(), self.pointer);
+        let mut select = Select::new(&items, buffer);
 
-            if let Some(pointer) = self.not_selected_pointer {
-                line.not_selected_pointer(pointer);
-            }
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        select.out.clear();
+        Select::erase_printed_items(&mut select).unwrap();
 
-            if line.len() > self.longest_item_len {
-                self.longest_item_len = line.len()
-            }
-            lines.push(line);
-            item_count += 1;
-        }
-        self.lines = lines;
-        self.item_count = item_count;
+        let output = String::from_utf8(select.out).unwrap();
+
+        assert!(output.starts_with("\x1b[7F"));
+        assert!(output.ends_with("\x1b[7F"));
+        assert_eq!(output.matches("\r\n").count(), 7);
     }
-    fn print_lines(&mut self) {
-        self.lines.iter_mut().for_each(|line| line.default());
 
-        self.lines[self.selected_item].select();
+    #[test]
+    fn not_selected_pointer_printed_on_unselected_lines() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
 
-        if self.underline_selected_item {
-            self.lines[self.selected_item].underline();
-        }
-        if self.move_selected_item_forward {
-            self.lines[self.selected_item].space_from_pointer(2);
-        }
+        let mut select = Select::new(&items, buffer);
+        select.pointer('◉').not_selected_pointer('○');
 
-        for line in self.lines.iter() {
-            writeln!(&mut self.out, "{}", line).unwrap()
-        }
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "◉ item1\r\n○ item2\r\n○ item3\r\n",
+            String::from_utf8(select.out).unwrap()
+        )
     }
 
-    fn erase_printed_items(&self) {
-        self.move_n_lines_up(self.item_count + 1);
+    #[test]
+    fn toggling_items_prints_checkboxes() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.multi_select();
+
+        Select::build_lines(&mut select);
+        select.selected_item = 1;
+        Select::toggle_selected_item(&mut select).unwrap();
 
-        for line in &self.lines {
-            println!("{}", " ".repeat(line.len()));
-        }
-        self.move_n_lines_up(self.item_count + 1);
-    }
-    fn move_n_lines_up(&self, n: usize) {
-        println!("[33[{}A", n);
+        assert!(String::from_utf8(select.out)
+            .unwrap()
+            .ends_with("  [ ] item1\r\n> [x] item2\r\n  [ ] item3\r\n"));
+        assert!(select.checked_items.contains(&1));
     }
 
-    fn move_up(&mut self) {
-        if self.selected_item == 0 {
-            return;
-        };
-        self.selected_item -= 1;
-        self.erase_printed_items();
-        self.print_lines();
+    #[test]
+    fn initial_index_is_clamped_to_last_item() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.initial_index(10);
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "  item1\r\n  item2\r\n> item3\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
     }
-    fn move_down(&mut self) {
-        if self.selected_item == self.items.len() - 1 {
-            return;
-        }
 
-        self.selected_item += 1;
-        self.erase_printed_items();
-        self.print_lines();
+    #[test]
+    fn initial_index_on_empty_list_is_zero() {
+        let items: Vec<&str> = vec![];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.initial_index(3);
+
+        assert_eq!(select.selected_item, 0);
     }
-    fn call_event_handler_if_supplied(&self, key: SelectDialogKey) {
-        if let Some(event_handler) = self.selection_changed.as_ref() {
-            let current_item = &self.items[self.selected_item];
-            event_handler(key, current_item);
-        }
+
+    #[test]
+    fn moving_up_on_first_item_wraps_around() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.wrap_around();
+
+        Select::build_lines(&mut select);
+        Select::move_up(&mut select).unwrap();
+
+        assert!(String::from_utf8(select.out)
+            .unwrap()
+            .ends_with("  item1\r\n  item2\r\n> item3\r\n"));
+        assert_eq!(select.selected_item, 2);
     }
-    /// Starts the Select Dialog and waits for the users input. The return is a reference to the chosen item
-    pub fn start(&mut self) -> &I {
-        self.build_lines();
-        self.print_lines();
 
-        self.up_keys.push(self.default_up);
-        self.down_keys.push(self.default_down);
+    #[test]
+    fn moving_down_on_last_item_wraps_around() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
 
-        loop {
-            let _ = enable_raw_mode();
-            let event = read().unwrap();
-            let _ = disable_raw_mode();
-            if event == Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)) {
-                break;
-            }
-            if self.event_contains_key(event.clone(), &self.up_keys) {
-                self.move_up();
-                self.call_event_handler_if_supplied(SelectDialogKey::UpKey);
-                continue;
-            } else if self.event_contains_key(event, &self.down_keys) {
-                self.move_down();
-                self.call_event_handler_if_supplied(SelectDialogKey::DownKey);
-                continue;
-            }
-        }
+        let mut select = Select::new(&items, buffer);
+        select.wrap_around().initial_index(2);
+
+        Select::build_lines(&mut select);
+        Select::move_down(&mut select).unwrap();
 
-        &self.items[self.selected_item]
+        assert_eq!(select.selected_item, 0);
     }
-    fn event_contains_key(&self, event: Event, keys: &[KeyCode]) -> bool {
-        for key in keys.iter() {
-            if event == Event::Key(KeyEvent::new(key.clone(), KeyModifiers::NONE)) {
-                return true;
-            }
-        }
-        false
+
+    #[test]
+    fn page_down_is_clamped_to_last_item() {
+        let items = vec!["1", "2", "3", "4", "5"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.page_size(3);
+
+        Select::build_lines(&mut select);
+        Select::move_page_down(&mut select).unwrap();
+        assert_eq!(select.selected_item, 3);
+
+        Select::move_page_down(&mut select).unwrap();
+        assert_eq!(select.selected_item, 4);
+
+        Select::move_page_up(&mut select).unwrap();
+        assert_eq!(select.selected_item, 1);
+
+        Select::move_page_up(&mut select).unwrap();
+        assert_eq!(select.selected_item, 0);
     }
-    /// Set a custom pointer to show in the select dialog
-    pub fn pointer(&mut self, pointer: char) -> &mut Self {
-        self.pointer = pointer;
-        self
+
+    #[test]
+    fn only_visible_rows_are_printed() {
+        let items = vec!["item1", "item2", "item3", "item4", "item5"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.visible_rows(2).initial_index(2);
+
+        Select::build_lines(&mut select);
+        Select::update_window_size(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "↑ more\r\n  item2\r\n> item3\r\n↓ more\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
     }
-    pub fn set_up_key(&mut self, key: KeyCode) -> &mut Self {
-        self.default_up = key;
-        self
+
+    #[test]
+    fn window_scrolls_with_selection() {
+        let items = vec!["item1", "item2", "item3", "item4"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.visible_rows(2);
+
+        Select::build_lines(&mut select);
+        Select::update_window_size(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        Select::move_down(&mut select).unwrap();
+        Select::move_down(&mut select).unwrap();
+        select.out.clear();
+        Select::move_down(&mut select).unwrap();
+
+        assert!(String::from_utf8(select.out)
+            .unwrap()
+            .ends_with("↑ more\r\n  item3\r\n> item4\r\n\r\n"));
+        assert_eq!(select.scroll_offset, 2);
     }
-    pub fn set_down_key(&mut self, key: KeyCode) -> &mut Self {
-        self.default_down = key;
-        self
+
+    #[test]
+    fn title_printed_above_items() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.title("Select an item:");
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        Select::erase_printed_items(&mut select).unwrap();
+
+        assert!(String::from_utf8(select.out)
+            .unwrap()
+            .starts_with("Select an item:\r\n> item1\r\n  item2\r\n\x1b[3F"));
     }
-    pub fn not_selected_pointer(&mut self, pointer: char) -> &mut Self {
-        self.not_selected_pointer = Some(pointer);
-        self
+
+    #[test]
+    fn moving_redraws_only_changed_lines() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        select.out.clear();
+        Select::move_down(&mut select).unwrap();
+
+        assert_eq!(
+            "\x1b[3F       \r  item1\x1b[3E\x1b[2F       \r> item2\x1b[2E",
+            String::from_utf8(select.out).unwrap()
+        );
     }
-    pub fn move_selected_item_forward(&mut self) -> &mut Self {
-        self.move_selected_item_forward = true;
-        self
+
+    #[test]
+    fn filter_shows_matching_items() {
+        let items = vec!["apple", "banana", "cherry", "pineapple"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.filterable();
+
+        Select::build_lines(&mut select);
+        select.query.push_str("APP");
+        Select::filter_items(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "Filter: APP\r\n> apple\r\n  pineapple\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
     }
-    #[cfg(feature = "color")]
-    pub fn color_selected_item(&mut self) -> &mut Self {
-        use crossterm::style::Color;
 
-        Color
+    #[test]
+    fn moving_down_uses_filtered_items() {
+        let items = vec!["apple", "banana", "cherry", "pineapple"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.filterable();
+
+        Select::build_lines(&mut select);
+        select.query.push('p');
+        Select::filter_items(&mut select);
+        Select::move_down(&mut select).unwrap();
+
+        assert_eq!(select.selected_item, 3);
     }
-    pub fn underline_selected_item(&mut self) -> &mut Self {
-        self.underline_selected_item = true;
-        self
+
+    #[test]
+    fn help_text_printed_below_items() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.help_text("enter to select");
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "> item1\r\n  item2\r\n\x1b[2menter to select\x1b[22m\r\n",
+            String::from_utf8(select.out.clone()).unwrap()
+        );
+        select.out.clear();
+        Select::move_down(&mut select).unwrap();
+
+        assert_eq!(
+            "\x1b[3F       \r  item1\x1b[3E\x1b[2F       \r> item2\x1b[2E",
+            String::from_utf8(select.out).unwrap()
+        );
     }
-    pub fn add_up_key(&mut self, key: KeyCode) -> &mut Self {
-        self.panic_if_key_is_enter(key);
-        self.up_keys.push(key);
-        self
+
+    #[test]
+    fn render_with_replaces_item_text() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.render_with(|item, selected| {
+            if selected {
+                item.to_uppercase()
+            } else {
+                item.to_string()
+            }
+        });
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "> ITEM1\r\n  item2\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
     }
-    pub fn add_down_key(&mut self, key: KeyCode) -> &mut Self {
-        self.panic_if_key_is_enter(key);
-        self.down_keys.push(key);
+
+    #[test]
+    fn char_keys_match_regardless_of_case() {
+        let items = vec!["item1", "item2"];
+        let select = Select::new(&items, vec![]);
+        let keys = [KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE)];
+
+        let lowercase = Event::Key(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
+        let uppercase = Event::Key(KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT));
+        let control = Event::Key(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+
+        assert!(select.event_contains_key(&lowercase, &keys));
+        assert!(select.event_contains_key(&uppercase, &keys));
+        assert!(!select.event_contains_key(&control, &keys));
+        assert!(select.event_contains_key(
+            &lowercase,
+            &[KeyEvent::new(KeyCode::Char('K'), KeyModifiers::NONE)]
+        ));
+    }
+
+    #[test]
+    fn keys_with_modifiers_match_only_with_modifiers() {
+        let items = vec!["item1", "item2"];
+        let mut select = Select::new(&items, vec![]);
+        select
+            .add_down_key_with_modifiers(KeyCode::Char('n'), KeyModifiers::CONTROL)
+            .unwrap();
+
+        let control = Event::Key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        let plain = Event::Key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        assert!(select.event_contains_key(&control, &select.config.key_map.down));
+        assert!(!select.event_contains_key(&plain, &select.config.key_map.down));
+    }
+
+    #[test]
+    fn numbers_printed_in_front_of_items() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.with_numbers();
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        let two = Event::Key(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE));
+        let three = Event::Key(KeyEvent::new(KeyCode::Char('3'), KeyModifiers::NONE));
+
+        assert_eq!(select.number_key_index(&two), Some(1));
+        assert_eq!(select.number_key_index(&three), None);
+        assert_eq!(
+            "> 1. item1\r\n  2. item2\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
+    }
+
+    #[test]
+    fn flow_handler_decides_how_to_continue() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.on_selection_changed_flow(|_, item| {
+            if *item == "item2" {
+                FlowControl::Confirm
+            } else {
+                FlowControl::Continue
+            }
+        });
+
+        assert_eq!(
+            select.call_event_handler_if_supplied(SelectDialogKey::Initial),
+            FlowControl::Continue
+        );
+        select.selected_item = 1;
+        assert_eq!(
+            select.call_event_handler_if_supplied(SelectDialogKey::DownKey),
+            FlowControl::Confirm
+        );
+    }
+
+    #[test]
+    fn navigation_skips_disabled_items() {
+        let items = vec!["item1", "item2", "item3", "item4"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.disabled_indices(&[1, 2]);
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        Select::move_down(&mut select).unwrap();
+        assert_eq!(select.selected_item, 3);
+
+        Select::move_up(&mut select).unwrap();
+        assert_eq!(select.selected_item, 0);
+
+        assert!(String::from_utf8(select.out).unwrap().starts_with(
+            "> item1\r\n\x1b[2m  item2\x1b[22m\r\n\x1b[2m  item3\x1b[22m\r\n  item4\r\n"
+        ));
+    }
+
+    #[test]
+    fn group_headers_printed_above_their_items() {
+        let items = vec!["recent", "favorite1", "favorite2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.group("Recent", 0..1).group("Favorites", 1..3);
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(select.row_of_item(1), 3);
+        assert_eq!(
+            "\x1b[1mRecent\x1b[22m\r\n> recent\r\n\x1b[1mFavorites\x1b[22m\r\n  favorite1\r\n  favorite2\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
+    }
+    #[test]
+    fn group_header_stays_visible_while_scrolling() {
+        let items = vec!["item1", "item2", "item3", "item4"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.group("All", 0..4).visible_rows(2).initial_index(3);
+
+        Select::build_lines(&mut select);
+        Select::update_window_size(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert!(String::from_utf8(select.out)
+            .unwrap()
+            .starts_with("↑ more\r\n\x1b[1mAll\x1b[22m\r\n  item3\r\n> item4\r\n"));
+    }
+
+    #[test]
+    fn vim_keys_move_and_jump() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.add_down_key(KeyCode::Char('n')).unwrap().vim_keys();
+
+        Select::build_lines(&mut select);
+
+        let key = |c: char, modifiers| Event::Key(KeyEvent::new(KeyCode::Char(c), modifiers));
+
+        assert_eq!(
+            select.navigate(&key('j', KeyModifiers::NONE)).unwrap(),
+            Some(SelectDialogKey::DownKey)
+        );
+        assert_eq!(
+            select.navigate(&key('n', KeyModifiers::NONE)).unwrap(),
+            Some(SelectDialogKey::DownKey)
+        );
+        assert_eq!(select.selected_item, 2);
+
+        assert_eq!(
+            select.navigate(&key('g', KeyModifiers::NONE)).unwrap(),
+            Some(SelectDialogKey::HomeKey)
+        );
+        assert_eq!(select.selected_item, 0);
+
+        assert_eq!(
+            select.navigate(&key('G', KeyModifiers::SHIFT)).unwrap(),
+            Some(SelectDialogKey::EndKey)
+        );
+        assert_eq!(select.selected_item, 2);
+    }
+    #[test]
+    fn half_page_keys_move_by_half_of_the_visible_rows() {
+        let items: Vec<usize> = (0..20).collect();
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.vim_keys();
+
+        Select::build_lines(&mut select);
+        select.window_size = 6;
+
+        let key = |c: char| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+
+        assert_eq!(
+            select.navigate(&key('d')).unwrap(),
+            Some(SelectDialogKey::HalfPageDownKey)
+        );
+        assert_eq!(select.selected_item, 3);
+
+        select.navigate(&key('u')).unwrap();
+        select.navigate(&key('u')).unwrap();
+        assert_eq!(select.selected_item, 0);
+
+        assert_eq!(
+            select
+                .navigate(&Event::Key(KeyEvent::new(
+                    KeyCode::Char('d'),
+                    KeyModifiers::NONE
+                )))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn rows_below_title_map_to_items() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.title("Choose");
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(select.item_at_row(0), None);
+        assert_eq!(select.item_at_row(1), Some(0));
+        assert_eq!(select.item_at_row(2), Some(1));
+        assert_eq!(select.item_at_row(3), None);
+    }
+    #[test]
+    fn scroll_wheel_moves_selection() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+
+        Select::build_lines(&mut select);
+
+        let scroll_down = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!(
+            select.navigate(&scroll_down).unwrap(),
+            Some(SelectDialogKey::DownKey)
+        );
+        assert_eq!(select.selected_item, 1);
+    }
+
+    #[test]
+    fn back_tab_matches_with_shift() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.tab_navigation();
+
+        let tab = Event::Key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        let back_tab = Event::Key(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT));
+
+        assert!(select.event_contains_key(&tab, &select.config.key_map.down));
+        assert!(select.event_contains_key(&back_tab, &select.config.key_map.up));
+        assert!(!select.event_contains_key(&back_tab, &select.config.key_map.down));
+    }
+
+    #[test]
+    fn clear_on_exit_erases_dialog() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.clear_on_exit();
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        select.out.clear();
+        Select::finish(&mut select, true).unwrap();
+
+        assert_eq!(
+            "\x1b[2F       \r\n       \r\n\x1b[2F",
+            String::from_utf8(select.out).unwrap()
+        );
+    }
+
+    #[test]
+    fn report_selection_replaces_dialog_with_chosen_item() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.report_selection("✔ Selected:").initial_index(1);
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        select.out.clear();
+        Select::finish(&mut select, true).unwrap();
+
+        assert!(String::from_utf8(select.out)
+            .unwrap()
+            .ends_with("\x1b[2F✔ Selected: item2\r\n"));
+    }
+
+    #[test]
+    fn label_function_creates_item_text() {
+        let items = vec![(1, "one"), (2, "two")];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::with_label(&items, buffer, |item| item.1.to_string());
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!("> one\r\n  two\r\n", String::from_utf8(select.out).unwrap());
+    }
+
+    #[test]
+    fn no_color_prints_selected_item_without_colors() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select
+            .selected_fg(Color::Red)
+            .selected_bg(Color::Blue)
+            .no_color();
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "> item1\r\n  item2\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
+    }
+
+    #[test]
+    fn items_start_at_first_column_without_pointer() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.no_pointer();
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(select.printed_widths, vec![5, 5]);
+        assert_eq!("item1\r\nitem2\r\n", String::from_utf8(select.out).unwrap());
+    }
+
+    #[test]
+    fn starting_twice_prints_the_dialog_again() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+
+        assert_eq!(Select::begin(&mut select).unwrap(), None);
+        Select::move_down(&mut select).unwrap();
+        select.out.clear();
+
+        assert_eq!(Select::begin(&mut select).unwrap(), None);
+        assert_eq!(
+            "  item1\r\n> item2\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
+        assert_eq!(select.config.key_map.up.len(), 1);
+    }
+    #[test]
+    fn reset_highlights_initial_item_again() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.initial_index(1).filterable();
+        select.query.push('3');
+
+        Select::build_lines(&mut select);
+        assert_eq!(select.selected_item, 2);
+
+        select.reset();
+        Select::build_lines(&mut select);
+        assert_eq!(select.selected_item, 1);
+        assert_eq!(select.visible_items, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn max_visible_limits_window() {
+        let items = vec!["1", "2", "3", "4", "5"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.max_visible(2);
+
+        Select::update_window_size(&mut select);
+        assert_eq!(select.window_size, 2);
+
+        select.max_visible(10);
+        Select::update_window_size(&mut select);
+        assert_eq!(select.window_size, 5);
+    }
+
+    #[test]
+    fn selected_item_indented_by_given_spaces() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.indent_selected(3);
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(select.printed_widths, vec![10, 7]);
+        assert_eq!(
+            ">    item1\r\n  item2\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
+    }
+
+    #[test]
+    fn rows_wrapped_by_narrow_terminal_are_erased() {
+        let items = vec!["a long item", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        select.out.clear();
+
+        select.terminal_width = Some(8);
+        Select::erase_printed_items(&mut select).unwrap();
+
+        assert!(String::from_utf8(select.out)
+            .unwrap()
+            .starts_with("\x1b[3F"));
+    }
+
+    #[test]
+    fn type_ahead_cycles_through_matching_items() {
+        let items = vec!["apple", "banana", "avocado", "cherry"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.type_ahead();
+
+        Select::build_lines(&mut select);
+
+        let a = Event::Key(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT));
+
+        assert_eq!(select.handle_event(&a).unwrap(), None);
+        assert_eq!(select.selected_item, 2);
+        assert_eq!(select.handle_event(&a).unwrap(), None);
+        assert_eq!(select.selected_item, 0);
+    }
+
+    #[test]
+    fn pressed_keys_are_recorded() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+
+        let down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        let end = Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+
+        assert_eq!(Select::begin(&mut select).unwrap(), None);
+        select.handle_event(&down).unwrap();
+        select.handle_event(&end).unwrap();
+
+        assert_eq!(
+            select.key_history(),
+            &[
+                SelectDialogKey::Initial,
+                SelectDialogKey::DownKey,
+                SelectDialogKey::EndKey
+            ]
+        );
+    }
+
+    #[test]
+    fn position_indicator_shows_selected_position() {
+        let items = vec!["1", "2", "3", "4", "5"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.visible_rows(3).position_indicator();
+
+        Select::build_lines(&mut select);
+        select.window_size = 3;
+        Select::print_lines(&mut select).unwrap();
+
+        assert!(String::from_utf8(select.out.clone())
+            .unwrap()
+            .ends_with("  3\r\n↓ more 1/5\r\n"));
+
+        select.out.clear();
+        Select::move_down(&mut select).unwrap();
+
+        assert!(String::from_utf8(select.out)
+            .unwrap()
+            .contains("↓ more 2/5"));
+    }
+
+    #[test]
+    fn confirm_twice_requires_second_enter() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.confirm_twice();
+
+        let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+
+        assert_eq!(Select::begin(&mut select).unwrap(), None);
+        assert_eq!(select.handle_event(&enter).unwrap(), None);
+        assert!(String::from_utf8(select.out.clone())
+            .unwrap()
+            .ends_with("Press enter again to confirm\r\n"));
+
+        assert_eq!(select.handle_event(&down).unwrap(), None);
+        assert_eq!(select.handle_event(&enter).unwrap(), None);
+        assert_eq!(select.handle_event(&enter).unwrap(), Some(true));
+        assert_eq!(select.selected_item, 1);
+    }
+
+    #[test]
+    fn releasing_enter_keeps_dialog_armed_and_notice_shown() {
+        let items = vec!["item1", "item2"];
+        let press = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let release = Event::Key(KeyEvent::new_with_kind(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+            KeyEventKind::Release,
+        ));
+
+        let mut select = Select::new(&items, Vec::new());
+        select.confirm_twice();
+        Select::begin(&mut select).unwrap();
+        assert_eq!(select.handle_event(&press).unwrap(), None);
+        assert_eq!(select.handle_event(&release).unwrap(), None);
+        assert!(select.armed);
+        assert_eq!(select.handle_event(&press).unwrap(), Some(true));
+
+        let mut select = Select::new(&items, Vec::new());
+        select.multi_select().min_selections(1);
+        Select::begin(&mut select).unwrap();
+        assert_eq!(select.handle_event(&press).unwrap(), None);
+        assert_eq!(select.handle_event(&release).unwrap(), None);
+        assert_eq!(select.notice.as_deref(), Some("Select at least 1 item"));
+    }
+
+    #[test]
+    fn annotations_aligned_in_column() {
+        let items = vec!["open", "save as"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.annotation_fn(|item| format!("^{}", item.chars().next().unwrap()));
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "> open    \x1b[2m^o\x1b[22m\r\n  save as \x1b[2m^s\x1b[22m\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
+    }
+
+    #[test]
+    fn horizontal_layout_prints_items_in_one_row() {
+        let items = vec!["Yes", "No", "Cancel"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.horizontal();
+
+        Select::build_lines(&mut select);
+        Select::update_window_size(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(select.printed_widths, vec![21]);
+        assert_eq!(
+            "> Yes    No    Cancel\r\n",
+            String::from_utf8(select.out.clone()).unwrap()
+        );
+
+        let right = Event::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        assert_eq!(
+            select.navigate(&right).unwrap(),
+            Some(SelectDialogKey::DownKey)
+        );
+        assert_eq!(select.selected_item, 1);
+    }
+
+    #[test]
+    fn no_matches_printed_for_empty_filter_result() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+        select.filterable().initial_index(1);
+        select.query.push('x');
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "Filter: x\r\n\x1b[2mNo matches\x1b[22m\r\n",
+            String::from_utf8(select.out.clone()).unwrap()
+        );
+        let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let backspace = Event::Key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+
+        assert_eq!(select.handle_event(&enter).unwrap(), None);
+        assert_eq!(select.handle_event(&backspace).unwrap(), None);
+        assert_eq!(select.visible_items, vec![0, 1]);
+        assert_eq!(select.selected_item, 1);
+    }
+
+    #[test]
+    fn keys_from_slice_are_added_together() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+
+        let mut select = Select::new(&items, buffer);
+
+        assert!(select
+            .up_keys(&[KeyCode::Char('k'), KeyCode::Char('w')])
+            .is_ok());
+        assert_eq!(select.config.key_map.up.len(), 3);
+
+        assert_eq!(
+            select
+                .down_keys(&[KeyCode::Char('j'), KeyCode::Enter])
+                .err(),
+            Some(SelectError::EnterKeyNotSupported)
+        );
+        assert_eq!(select.config.key_map.down, KeyMap::default().down);
+    }
+    #[test]
+    fn key_map_replaces_cancel_keys() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        let key_map = KeyMap {
+            cancel: vec![KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)],
+            ..KeyMap::default()
+        };
+        select.key_map(key_map).unwrap();
+
+        let esc = Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        let q = Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+
+        assert_eq!(select.handle_event(&esc).unwrap(), None);
+        assert_eq!(select.handle_event(&q).unwrap(), Some(false));
+    }
+    #[test]
+    fn key_map_with_enter_as_move_key_is_rejected() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        let mut key_map = KeyMap::default();
+        key_map
+            .down
+            .push(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            select.key_map(key_map).err(),
+            Some(SelectError::EnterKeyNotSupported)
+        );
+        assert_eq!(select.config.key_map, KeyMap::default());
+    }
+    #[test]
+    fn set_up_key_replaces_the_arrow() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.set_up_key(KeyCode::Char('w'));
+
+        assert_eq!(
+            select.config.key_map.up,
+            vec![KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE)]
+        );
+    }
+    #[test]
+    fn confirming_with_too_few_checked_items_shows_notice() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.multi_select().min_selections(2);
+        Select::begin(&mut select).unwrap();
+
+        let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let space = Event::Key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+
+        assert_eq!(select.handle_event(&space).unwrap(), None);
+        select.out.clear();
+        assert_eq!(select.handle_event(&enter).unwrap(), None);
+        assert!(String::from_utf8(select.out.clone())
+            .unwrap()
+            .ends_with("Select at least 2 items\r\n"));
+
+        select.move_down().unwrap();
+        assert_eq!(select.handle_event(&space).unwrap(), None);
+        assert_eq!(select.notice, None);
+        assert_eq!(select.handle_event(&enter).unwrap(), Some(true));
+    }
+    #[test]
+    fn checking_more_than_max_selections_is_rejected() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.multi_select().max_selections(1);
+        Select::begin(&mut select).unwrap();
+
+        select.toggle_selected_item().unwrap();
+        select.move_down().unwrap();
+        select.out.clear();
+        select.toggle_selected_item().unwrap();
+
+        assert_eq!(select.checked_items, HashSet::from([0]));
+        assert!(String::from_utf8(select.out.clone())
+            .unwrap()
+            .ends_with("Select at most 1 item\r\n"));
+
+        select.move_up().unwrap();
+        select.toggle_selected_item().unwrap();
+        select.move_down().unwrap();
+        select.toggle_selected_item().unwrap();
+        assert_eq!(select.checked_items, HashSet::from([1]));
+    }
+    #[test]
+    fn paginated_list_shows_one_page() {
+        let items = vec!["item1", "item2", "item3", "item4", "item5"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.paginate(2);
+        Select::begin(&mut select).unwrap();
+
+        assert_eq!(
+            "> item1\r\n  item2\r\nPage 1/3\r\n",
+            String::from_utf8(select.out.clone()).unwrap()
+        );
+
+        select.move_down().unwrap();
+        select.move_down().unwrap();
+        assert_eq!(select.selected_item, 1);
+
+        select.switch_page(true).unwrap();
+        select.switch_page(true).unwrap();
+        assert_eq!(select.selected_item, 4);
+        assert!(String::from_utf8(select.out.clone())
+            .unwrap()
+            .ends_with("> item5\r\nPage 3/3\r\n"));
+
+        select.switch_page(true).unwrap();
+        assert_eq!(select.selected_item, 4);
+        select.move_up().unwrap();
+        assert_eq!(select.selected_item, 4);
+        select.switch_page(false).unwrap();
+        assert_eq!(select.selected_item, 2);
+    }
+    #[test]
+    fn throttled_handler_is_called_once_with_last_key() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let recorded = calls.clone();
+        let mut select = Select::new(&items, buffer);
+        select
+            .throttle(Duration::from_millis(50))
+            .on_selection_changed(move |key, item| recorded.borrow_mut().push((key, *item)));
+        Select::begin(&mut select).unwrap();
+
+        let down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        select.handle_event(&down).unwrap();
+        select.handle_event(&down).unwrap();
+
+        assert_eq!(*calls.borrow(), vec![(SelectDialogKey::Initial, "item1")]);
+        assert_eq!(select.call_pending_handler(), None);
+        assert_eq!(
+            *calls.borrow(),
+            vec![
+                (SelectDialogKey::Initial, "item1"),
+                (SelectDialogKey::DownKey, "item3")
+            ]
+        );
+        assert_eq!(select.key_history().len(), 3);
+        assert_eq!(select.call_pending_handler(), None);
+        assert_eq!(calls.borrow().len(), 2);
+    }
+    #[test]
+    fn theme_styles_selected_item() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select
+            .theme(Theme {
+                pointer: String::from("▶"),
+                not_selected_pointer: Some('·'),
+                ..Theme::default()
+            })
+            .bold_selected_item();
+        Select::begin(&mut select).unwrap();
+
+        assert_eq!(
+            "▶ \x1b[1mitem1\x1b[22m\r\n· item2\r\n",
+            String::from_utf8(select.out.clone()).unwrap()
+        );
+        assert!(select.config.theme.bold_selected_item);
+    }
+    #[test]
+    fn headless_index_is_only_used_without_terminal() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+
+        assert_eq!(select.headless_index(false), None);
+
+        select.headless(5);
+        assert_eq!(select.headless_index(false), Some(2));
+        assert_eq!(select.headless_index(true), None);
+    }
+    #[test]
+    fn scroll_off_keeps_items_around_selection_visible() {
+        let items = vec![
+            "item1", "item2", "item3", "item4", "item5", "item6", "item7",
+        ];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.visible_rows(4).scroll_off(1);
+        Select::begin(&mut select).unwrap();
+
+        select.move_down().unwrap();
+        select.move_down().unwrap();
+        assert_eq!(select.scroll_offset, 0);
+        select.move_down().unwrap();
+        assert_eq!(select.scroll_offset, 1);
+
+        Select::move_to_last(&mut select).unwrap();
+        assert_eq!(select.scroll_offset, 3);
+        select.move_up().unwrap();
+        select.move_up().unwrap();
+        assert_eq!(select.scroll_offset, 3);
+        select.move_up().unwrap();
+        assert_eq!(select.scroll_offset, 2);
+    }
+    #[test]
+    fn unhandled_key_is_passed_to_handler() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.on_unhandled_key(|key| match key.code {
+            KeyCode::Char('q') => FlowControl::Cancel,
+            KeyCode::F(1) => FlowControl::Confirm,
+            _ => FlowControl::Continue,
+        });
+        Select::begin(&mut select).unwrap();
+
+        let down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        let x = Event::Key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        let q = Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        let f1 = Event::Key(KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE));
+
+        assert_eq!(select.handle_event(&down).unwrap(), None);
+        assert_eq!(select.handle_event(&x).unwrap(), None);
+        assert_eq!(select.handle_event(&q).unwrap(), Some(false));
+        assert_eq!(select.handle_event(&f1).unwrap(), Some(true));
+    }
+    #[test]
+    fn released_characters_are_ignored() {
+        let items = vec!["apple", "banana", "avocado"];
+        let release = Event::Key(KeyEvent::new_with_kind(
+            KeyCode::Char('a'),
+            KeyModifiers::NONE,
+            KeyEventKind::Release,
+        ));
+
+        let mut select = Select::new(&items, Vec::new());
+        select.filterable();
+        Select::begin(&mut select).unwrap();
+        select.handle_event(&release).unwrap();
+        assert_eq!(select.query, "");
+
+        let mut select = Select::new(&items, Vec::new());
+        select.type_ahead();
+        Select::begin(&mut select).unwrap();
+        select.handle_event(&release).unwrap();
+        assert_eq!(select.selected_item, 0);
+
+        let mut select = Select::new(&items, Vec::new());
+        select.on_unhandled_key(|_| FlowControl::Cancel);
+        Select::begin(&mut select).unwrap();
+        assert_eq!(select.handle_event(&release).unwrap(), None);
+    }
+    #[test]
+    fn home_end_and_initial_item_skip_disabled_items() {
+        let items = vec!["header", "item1", "item2", "footer"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.disabled_indices(&[0, 3]);
+        Select::begin(&mut select).unwrap();
+
+        assert_eq!(select.selected_item, 1);
+
+        let home = Event::Key(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+        let end = Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+
+        select.handle_event(&end).unwrap();
+        assert_eq!(select.selected_item, 2);
+        select.handle_event(&home).unwrap();
+        assert_eq!(select.selected_item, 1);
+    }
+    #[test]
+    fn builder_options_are_applied() {
+        let items = vec!["item1", "item2"];
+        let mut builder = SelectBuilder::new();
+        builder.title("Choose").with_numbers().page_size(3);
+
+        let mut select = builder.items(&items, Vec::new());
+        Select::begin(&mut select).unwrap();
+
+        assert_eq!(
+            "Choose\r\n> 1. item1\r\n  2. item2\r\n",
+            String::from_utf8(select.out.clone()).unwrap()
+        );
+        assert_eq!(select.config.page_size, 3);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_output_writes_chosen_items() {
+        let items = vec!["item1", "item2", "item3"];
+        let mut json: Vec<u8> = vec![];
+        let mut select = Select::new(&items, Vec::new());
+        select.json_output(&mut json);
+        Select::begin(&mut select).unwrap();
+
+        select.move_down().unwrap();
+        select.confirm(true).unwrap();
+
+        select.multi_select();
+        select.checked_items = HashSet::from([2, 0]);
+        select.confirm(true).unwrap();
+        drop(select);
+
+        assert_eq!(
+            "{\"index\":1,\"item\":\"item2\"}\n{\"index\":0,\"item\":\"item1\"}\n{\"index\":2,\"item\":\"item3\"}\n",
+            String::from_utf8(json).unwrap()
+        );
+    }
+    #[cfg(feature = "async")]
+    #[test]
+    fn spinner_is_erased_once_items_are_loaded() {
+        let buffer: Vec<u8> = vec![];
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        let mut select = Select::from_future(async { receiver.await.unwrap() }, buffer);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(10));
+            sender.send(vec!["item1", "item2"])
+        });
+        futures::executor::block_on(select.load_items()).unwrap();
+
+        assert_eq!(select.items(), &["item1", "item2"]);
+        assert!(select.printed_widths.is_empty());
+        assert!(String::from_utf8(select.out.clone())
+            .unwrap()
+            .starts_with("⠋ Loading…\r\n"));
+    }
+    #[test]
+    fn bordered_dialog_frames_rows_and_title() {
+        let items = vec!["item1", "a long item"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.bordered().title("Pick").max_width(13);
+        Select::begin(&mut select).unwrap();
+
+        assert_eq!(
+            "┌─ Pick ────┐\r\n│ > item1   │\r\n│   a long… │\r\n└───────────┘\r\n",
+            String::from_utf8(select.out.clone()).unwrap()
+        );
+        assert_eq!(select.printed_widths, vec![13, 13, 13, 13]);
+        assert_eq!(select.row_of_item(1), 2);
+    }
+    #[test]
+    fn set_items_clamps_and_update_items_keeps_selection() {
+        let items = vec!["item1", "item2", "item3"];
+        let fewer = vec!["item1", "item2"];
+        let moved = vec!["item0", "item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        Select::begin(&mut select).unwrap();
+        Select::move_to_last(&mut select).unwrap();
+
+        let printed = select.out.len();
+        select.set_items(&fewer);
+        assert_eq!(select.selected(), Some(&"item2"));
+        assert_eq!(select.out.len(), printed);
+
+        select.checked_items = HashSet::from([0]);
+        select.update_items(&moved);
+        assert_eq!(select.selected_index(), 2);
+        assert_eq!(select.checked_items, HashSet::from([1]));
+
+        select.selected_item = 1;
+        select.update_items(&fewer);
+        assert_eq!(select.selected(), Some(&"item1"));
+    }
+    #[test]
+    fn accessible_mode_prints_plain_lines() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.accessible().title("Pick").underline_selected_item();
+        Select::begin(&mut select).unwrap();
+
+        select.move_down().unwrap();
+        select.handle_event(&Event::Resize(80, 24)).unwrap();
+        select.finish(true).unwrap();
+
+        assert_eq!(
+            "Pick\r\nSelected: item1 (1 of 3)\r\nSelected: item2 (2 of 3)\r\nConfirmed: item2\r\n",
+            String::from_utf8(select.out.clone()).unwrap()
+        );
+        assert!(select.printed_widths.is_empty());
+    }
+    #[test]
+    fn change_handler_gets_old_and_new_index() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let events = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let recorded = events.clone();
+        let mut select = Select::new(&items, buffer);
+        select.on_change(move |event| {
+            recorded
+                .borrow_mut()
+                .push((event.key, event.old_index, event.new_index, *event.item))
+        });
+        Select::begin(&mut select).unwrap();
+
+        select.move_to_last().unwrap();
+        select.flow_result(SelectDialogKey::EndKey);
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                (SelectDialogKey::Initial, None, 0, "item1"),
+                (SelectDialogKey::EndKey, Some(0), 2, "item3"),
+            ]
+        );
+    }
+    #[test]
+    fn static_items_are_borrowed() {
+        static MENU: [&str; 3] = ["Open", "Save", "Quit"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&MENU, buffer);
+        Select::begin(&mut select).unwrap();
+        select.move_down().unwrap();
+
+        assert!(std::ptr::eq(select.selected().unwrap(), &MENU[1]));
+        assert!(std::ptr::eq(select.items(), &MENU[..]));
+    }
+    #[test]
+    fn removing_items_keeps_min_items() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.editable().min_items(2);
+        Select::begin(&mut select).unwrap();
+
+        let delete = Event::Key(KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE));
+        select.handle_event(&delete).unwrap();
+        select.handle_event(&delete).unwrap();
+        assert_eq!(select.visible_items, vec![1, 2]);
+        assert_eq!(select.notice.as_deref(), Some("Keep at least 2 items"));
+    }
+    #[test]
+    fn removing_all_items_keeps_the_last_one() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.editable().min_items(0);
+        Select::begin(&mut select).unwrap();
+        select.move_down().unwrap();
+
+        let delete = Event::Key(KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE));
+        select.handle_event(&delete).unwrap();
+        assert_eq!(select.visible_items, vec![0, 2]);
+        assert_eq!(select.selected(), Some(&"item3"));
+
+        select.handle_event(&delete).unwrap();
+        assert_eq!(select.selected(), Some(&"item1"));
+
+        select.handle_event(&delete).unwrap();
+        assert_eq!(select.visible_items, vec![0]);
+        assert_eq!(select.selected(), Some(&"item1"));
+        assert_eq!(select.notice.as_deref(), Some("Keep at least 1 item"));
+    }
+    #[test]
+    fn removing_the_only_enabled_item_blocks_confirming() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.editable().disabled_indices(&[1]);
+        Select::begin(&mut select).unwrap();
+
+        let delete = Event::Key(KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE));
+        let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(select.handle_event(&delete).unwrap(), None);
+        assert_eq!(select.visible_items, vec![1]);
+        assert_eq!(select.handle_event(&enter).unwrap(), None);
+    }
+    #[test]
+    fn released_number_key_does_not_confirm() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.with_numbers();
+        Select::begin(&mut select).unwrap();
+
+        let press = KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE);
+        let release = KeyEvent::new_with_kind(
+            KeyCode::Char('2'),
+            KeyModifiers::NONE,
+            KeyEventKind::Release,
+        );
+        assert_eq!(select.handle_event(&Event::Key(press)).unwrap(), None);
+        assert_eq!(select.handle_event(&Event::Key(release)).unwrap(), None);
+        assert_eq!(select.selected_item, 1);
+    }
+    #[test]
+    fn home_and_end_keys_jump_to_first_and_last_item() {
+        let items = vec!["item1", "item2", "item3", "item4"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+
+        Select::build_lines(&mut select);
+
+        let home = Event::Key(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+        let end = Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+
+        assert_eq!(
+            select.navigate(&end).unwrap(),
+            Some(SelectDialogKey::EndKey)
+        );
+        assert_eq!(select.selected_item, 3);
+        assert_eq!(
+            select.navigate(&home).unwrap(),
+            Some(SelectDialogKey::HomeKey)
+        );
+        assert_eq!(select.selected_item, 0);
+
+        select.disabled_indices(&[0, 3]);
+        Select::build_lines(&mut select);
+
+        assert_eq!(
+            select.navigate(&end).unwrap(),
+            Some(SelectDialogKey::EndKey)
+        );
+        assert_eq!(select.selected_item, 2);
+        assert_eq!(
+            select.navigate(&home).unwrap(),
+            Some(SelectDialogKey::HomeKey)
+        );
+        assert_eq!(select.selected_item, 1);
+    }
+    #[test]
+    fn armed_text_names_the_confirm_key() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+
+        assert_eq!(select.armed_text(), "Press enter again to confirm");
+
+        select.confirm_key(KeyCode::Char('l'));
+        assert_eq!(select.armed_text(), "Press l again to confirm");
+
+        select.confirm_key(KeyCode::Right);
+        assert_eq!(select.armed_text(), "Press right again to confirm");
+    }
+    #[test]
+    fn no_color_prints_title_and_help_text_plain() {
+        let items = vec!["item1"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select
+            .title("Pick")
+            .bold_title()
+            .help_text("enter to select");
+        select.config.no_color = true;
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+
+        assert_eq!(
+            "Pick\r\n> item1\r\nenter to select\r\n",
+            String::from_utf8(select.out).unwrap()
+        );
+    }
+    #[test]
+    fn render_with_renders_only_changed_lines() {
+        let items = vec!["item1", "item2", "item3"];
+        let buffer: Vec<u8> = vec![];
+        let calls = std::cell::Cell::new(0);
+
+        let mut select = Select::new(&items, buffer);
+        select.render_with(|item, selected| {
+            calls.set(calls.get() + 1);
+            format!("{}{}", item, if selected { " *" } else { "" })
+        });
+
+        Select::build_lines(&mut select);
+        Select::print_lines(&mut select).unwrap();
+        assert_eq!(calls.get(), 3);
+
+        select.out.clear();
+        Select::move_down(&mut select).unwrap();
+        assert_eq!(calls.get(), 5);
+        assert_eq!(select.lines[0].text(), "item1");
+        assert_eq!(select.lines[1].text(), "item2 *");
+    }
+    #[cfg(feature = "async")]
+    #[test]
+    fn initial_item_is_applied_once_items_are_loaded() {
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::from_future(async { vec!["item1", "item2", "item3"] }, buffer);
+        select.initial_index(2);
+        futures::executor::block_on(select.load_items()).unwrap();
+
+        assert_eq!(select.selected_index(), 2);
+
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::from_future(async { vec!["item1", "item2", "item3"] }, buffer);
+        select.initial_value_owned("item2");
+        futures::executor::block_on(select.load_items()).unwrap();
+
+        assert_eq!(select.selected_index(), 1);
+    }
+    #[cfg(feature = "async")]
+    #[test]
+    fn waiting_within_a_timeout_returns_ready_output_only() {
+        let pending = futures::future::pending::<()>();
+        let ready = futures::future::ready(1);
+
+        assert_eq!(
+            futures::executor::block_on(within(pending, Duration::from_millis(10))),
+            None
+        );
+        assert_eq!(
+            futures::executor::block_on(within(ready, Duration::from_secs(60))),
+            Some(1)
+        );
+    }
+    #[test]
+    fn alternate_screen_is_entered_on_the_dialog_output() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.alternate_screen();
+
+        let modes = select.enter_terminal_modes().unwrap();
+        assert!(String::from_utf8(select.out.clone())
+            .unwrap()
+            .starts_with("\x1b[?1049h"));
+
+        select.out.clear();
+        select.leave_terminal_modes(modes, Ok(true)).unwrap();
+        assert_eq!(
+            String::from_utf8(select.out.clone()).unwrap(),
+            "\x1b[?1049l"
+        );
+    }
+    #[test]
+    fn scripted_clicks_highlight_and_confirm_the_item_in_the_row() {
+        let items = vec!["item1", "item2", "item3"];
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 4,
+            row: 2,
+            modifiers: KeyModifiers::NONE,
+        });
+        let mut select = Select::new(&items, Vec::new());
+        select
+            .title("Pick")
+            .mouse()
+            .event_source(crate::VecEventSource::new([click.clone(), click]));
+
+        assert_eq!(select.start(), Ok(Some(&"item2")));
+    }
+    #[test]
+    fn key_presses_restart_the_timeout_unless_hard() {
+        let items = vec!["item1", "item2"];
+        let buffer: Vec<u8> = vec![];
+        let mut select = Select::new(&items, buffer);
+        select.timeout(Duration::from_secs(60));
+
+        let start = Instant::now();
+        let key = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        let resize = Event::Resize(80, 24);
+
+        assert_eq!(select.next_deadline(Some(start), &resize), Some(start));
+        assert!(
+            select.next_deadline(Some(start), &key).unwrap() >= start + Duration::from_secs(60)
+        );
+
+        select.hard_timeout();
+        assert_eq!(select.next_deadline(Some(start), &key), Some(start));
+    }
+    #[test]
+    fn builder_applies_behavior_and_style_options() {
+        let items = vec!["item1", "item2", "item3"];
+        let mut builder = SelectBuilder::new();
+        builder
+            .multi_select()
+            .bordered()
+            .timeout(Duration::from_secs(3))
+            .max_selections(2)
+            .editable()
+            .min_items(2)
+            .report_selection("Selected: ")
+            .pointer('*')
+            .dim_unselected()
+            .vim_keys();
+
+        let select = builder.items(&items, Vec::new());
+
+        assert!(select.config.multi_select && select.config.bordered && select.config.editable);
+        assert_eq!(select.config.timeout, Some(Duration::from_secs(3)));
+        assert_eq!(select.config.max_selections, Some(2));
+        assert_eq!(select.config.min_items, 2);
+        assert_eq!(select.config.report_prefix.as_deref(), Some("Selected: "));
+        assert_eq!(select.config.theme.pointer, "*");
+        assert!(select.config.theme.dim_unselected);
+        assert!(select
+            .config
+            .key_map
+            .down
+            .contains(&KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE)));
+    }
+}
+
+/// The items that are still loading, see `Select::from_future`
+#[cfg(feature = "async")]
+type ItemsFuture<'a, I> = Pin<Box<dyn Future<Output = Vec<I>> + 'a>>;
+
+/// Finds the initial item once the items of `from_future` are loaded
+#[cfg(feature = "async")]
+type InitialLookup<'a, I> = Box<dyn Fn(&[I]) -> usize + 'a>;
+
+/// Struct to create a select dialog and get the users chosen item
+///
+/// The input is retrieved over an endless loop. When the user presses enter,
+/// the loop stops and the chosen item is returned. Pressing escape or Ctrl+C stops the loop without a chosen item.
+///
+/// # Example
+///
+/// Create the dialog with default settings
+///
+/// ```no_run
+/// use cli_select::Select;
+/// use std::io::stdout;
+///
+/// let items = vec!["item1", "item2", "item3"];
+/// let selected_item = Select::new(&items, stdout()).start();
+/// ```
+///
+/// Customize dialog before starting
+///
+/// ```no_run
+/// use cli_select::{Select, KeyCode};
+/// use std::io::stdout;
+///
+/// # fn main() -> Result<(), cli_select::SelectError> {
+/// let items = vec!["item1", "item2", "item3"];
+/// let selected_item = Select::new(&items, stdout())
+///     .add_up_key(KeyCode::Char('j'))?
+///     .pointer('◉')
+///     .not_selected_pointer('𐩒')
+///     .underline_selected_item()
+///     .start()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Everything is printed to the writer passed to `new`, so the dialog can also render into a buffer
+///
+/// ```no_run
+/// use cli_select::Select;
+///
+/// let items = vec!["item1", "item2", "item3"];
+/// let mut select = Select::new(&items, Vec::new());
+/// let selected_item = select.start();
+///
+/// let printed = String::from_utf8_lossy(select.writer());
+/// ```
+pub struct Select<'a, I, W>
+where
+    W: Write, // W: std::io::Write, // F: Fn(SelectDialogKey, &I),
+{
+    items: Items<'a, I>,
+    label: ItemLabel<'a, I>,
+    annotation: Option<ItemLabel<'a, I>>,
+    lines: Vec<Line>,
+    selected_item: usize,
+    initial_item: usize,
+    pub selection_changed: Option<SelectionChange<I>>,
+    selection_flow: Option<SelectionFlow<I>>,
+    change_handler: Option<ChangeHandler<I>>,
+    reported_item: Option<usize>,
+    render: Option<ItemRenderer<'a, I>>,
+    rendered_item: usize,
+    unhandled_key: Option<KeyHandler>,
+    event_source: Option<Box<dyn EventSource + 'a>>,
+    #[cfg(feature = "serde")]
+    json_output: Option<JsonOutput<'a, I>>,
+    #[cfg(feature = "async")]
+    loading: Option<ItemsFuture<'a, I>>,
+    #[cfg(feature = "async")]
+    initial_lookup: Option<InitialLookup<'a, I>>,
+    announcement: Option<String>,
+    window_size: usize,
+    terminal_width: Option<usize>,
+    terminal_height: Option<usize>,
+    // the row of the terminal the dialog starts in, to find the clicked item
+    first_row: usize,
+    scroll_offset: usize,
+    printed_widths: Vec<usize>,
+    armed: bool,
+    notice: Option<String>,
+    pending_key: Option<SelectDialogKey>,
+    timed_out: bool,
+    query: String,
+    visible_items: Vec<usize>,
+    checked_items: HashSet<usize>,
+    disabled_items: HashSet<usize>,
+    removed_items: HashSet<usize>,
+    groups: Vec<(String, Range<usize>)>,
+    key_history: Vec<SelectDialogKey>,
+    longest_item_len: usize,
+    // the options that don't depend on the items, the setters of the same name change them
+    config: SelectBuilder,
+    out: W,
+    // out: Option<W>, // logger: Logger<W>,
+}
+
+impl<'a, I, W> Select<'a, I, W>
+where
+    I: Display + 'a,
+    W: std::io::Write,
+{
+    /// Create a new Select Dialog with lines defined in the items parameter. Any slice can be used,
+    /// for example a reference to a `Vec` or an array.
+    ///
+    /// The items are borrowed and never copied, so a static menu needs no `Vec`:
+    ///
+    /// ```no_run
+    /// use cli_select::Select;
+    ///
+    /// static MENU: [&str; 3] = ["Open", "Save", "Quit"];
+    ///
+    /// let selected_item = Select::new(&MENU, std::io::stdout()).start();
+    /// ```
+    ///
+    /// Allocation is small but not zero: when the dialog starts, it builds a line with the label of every item,
+    /// and the handlers are boxed. `start` returns a reference into the slice, only `start_owned` clones the chosen item.
+    ///
+    /// Any Struct that implements std::io::write can be used as output. Use std::io::stdout() as second parameter to print to console.
+    /// The output is flushed after every render, so a locked `std::io::stdout().lock()` works as well and avoids locking for every line.
+    ///
+    /// Use std::io::stderr() to keep stdout free for the chosen item, so the result can be captured in a shell
+    /// like `x=$(my-tool)`. The escape codes of mouse capture and the alternate screen are written to the same output.
+    pub fn new(items: &'a [I], out: W) -> Select<'a, I, W> {
+        Select::with_items(Items::Borrowed(items), out)
+    }
+    /// Create a new Select Dialog that owns the items collected from the given iterator.
+    ///
+    /// This is useful when the items are computed just for the dialog and should not outlive it.
+    pub fn from_iter(items: impl IntoIterator<Item = I>, out: W) -> Select<'a, I, W> {
+        Select::with_items(Items::Owned(items.into_iter().collect()), out)
+    }
+    /// Create a new Select Dialog for items that are still loading, for example from a server.
+    /// A spinner is printed until the future resolves, then the list is shown.
+    ///
+    /// `start_async` waits for the items without blocking, `start` blocks the thread while they load.
+    /// The spinner is moved on by a thread, that stops as soon as the items are loaded or the dialog is dropped.
+    #[cfg(feature = "async")]
+    pub fn from_future(items: impl Future<Output = Vec<I>> + 'a, out: W) -> Select<'a, I, W> {
+        let mut select = Select::with_items(Items::Owned(vec![]), out);
+        select.loading = Some(Box::pin(items));
+        select
+    }
+    fn with_items(items: Items<'a, I>, out: W) -> Select<'a, I, W> {
+        Select::with_items_and_label(items, out, Box::new(|item: &I| item.to_string()))
+    }
+}
+
+impl<'a, I, W> Select<'a, I, W>
+where
+    W: std::io::Write,
+{
+    /// Create a new Select Dialog for items that don't implement `Display`.
+    /// The label function returns the text that is shown for an item, `start` still returns the item itself.
+    pub fn with_label(
+        items: &'a [I],
+        out: W,
+        label: impl Fn(&I) -> String + 'a,
+    ) -> Select<'a, I, W> {
+        Select::with_items_and_label(Items::Borrowed(items), out, Box::new(label))
+    }
+    fn with_items_and_label(
+        items: Items<'a, I>,
+        out: W,
+        label: ItemLabel<'a, I>,
+    ) -> Select<'a, I, W> {
+        Select {
+            items,
+            label,
+            annotation: None,
+            selected_item: 0,
+            initial_item: 0,
+            selection_changed: None,
+            change_handler: None,
+            reported_item: None,
+            selection_flow: None,
+            render: None,
+            rendered_item: 0,
+            unhandled_key: None,
+            event_source: None,
+            #[cfg(feature = "serde")]
+            json_output: None,
+            #[cfg(feature = "async")]
+            loading: None,
+            #[cfg(feature = "async")]
+            initial_lookup: None,
+            announcement: None,
+            window_size: usize::MAX,
+            terminal_width: None,
+            terminal_height: None,
+            first_row: 0,
+            scroll_offset: 0,
+            printed_widths: vec![],
+            armed: false,
+            pending_key: None,
+            notice: None,
+            timed_out: false,
+            query: String::new(),
+            visible_items: vec![],
+            checked_items: HashSet::new(),
+            disabled_items: HashSet::new(),
+            removed_items: HashSet::new(),
+            groups: vec![],
+            key_history: vec![],
+            lines: vec![],
+            longest_item_len: 0,
+            config: SelectBuilder::default(),
+            out,
+        }
+    }
+    /// Builds the lines and store them for later usage. longest_item_len is initialized.
+    fn build_lines(&mut self) {
+        let mut lines: Vec<Line> = vec![];
+        for (index, item) in self.items.iter().enumerate() {
+            let text = match &self.render {
+                Some(render) => render(item, index == self.selected_item),
+                None => (self.label)(item),
+            };
+            let mut line = Line::new(text, ' ');
+            line.pointer(self.config.theme.pointer.as_str());
+
+            if let Some(pointer) = self.config.theme.not_selected_pointer {
+                line.not_selected_pointer(pointer);
+            }
+            if self.config.theme.no_pointer {
+                line.hide_pointer();
+            }
+            if self.config.with_numbers {
+                line.number(index + 1);
+                line.number_width(self.items.len().to_string().len());
+            }
+            if let Some(annotation) = self.annotation.as_ref().map(|annotate| annotate(item)) {
+                if !annotation.is_empty() {
+                    line.annotation(annotation);
+                }
+            }
+            if self.disabled_items.contains(&index) {
+                line.disable();
+            }
+
+            if line.len() > self.longest_item_len {
+                self.longest_item_len = line.len()
+            }
+            lines.push(line);
+        }
+        self.lines = lines;
+        self.rendered_item = self.selected_item;
+        self.filter_items();
+
+        if self.disabled_items.contains(&self.selected_item) {
+            // the initial item can't be chosen, the nearest enabled one is highlighted instead
+            let position = self.position();
+
+            if let Some(position) = self
+                .enabled_position(position, true)
+                .or_else(|| self.enabled_position(position, false))
+            {
+                self.selected_item = self.visible_items[position];
+            }
+        }
+    }
+    /// Shows only the items that contain the query, ignoring case. Without a query, all items are shown.
+    ///
+    /// If the selected item is filtered out, the first matching item that is not disabled is selected.
+    fn filter_items(&mut self) {
+        let query = self.query.to_lowercase();
+
+        self.visible_items = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(index, line)| {
+                !self.removed_items.contains(index) && line.text().to_lowercase().contains(&query)
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        if !self.visible_items.contains(&self.selected_item) {
+            if let Some(position) = self.enabled_position(0, true) {
+                self.selected_item = self.visible_items[position];
+            }
+        }
+        self.scroll_offset = 0;
+    }
+    /// Returns the position of the selected item in the list of visible items
+    fn position(&self) -> usize {
+        self.position_of(self.selected_item)
+    }
+    fn position_of(&self, index: usize) -> usize {
+        self.visible_items
+            .iter()
+            .position(|&item| item == index)
+            .unwrap_or(0)
+    }
+    fn print_lines(&mut self) -> std::io::Result<()> {
+        if self.config.accessible {
+            return self.announce();
+        }
+        self.style_lines();
+        self.scroll_to_selected_item();
+
+        let window_end =
+            (self.scroll_offset.saturating_add(self.window_size)).min(self.visible_items.len());
+        let is_scrolling = self.is_scrolling();
+
+        let mut rows: Vec<(String, usize)> = vec![];
+
+        if let Some(title) = self.config.title.as_ref().filter(|_| !self.config.bordered) {
+            let width = title.width();
+
+            if self.config.theme.bold_title {
+                rows.push((self.bold(title), width));
+            } else {
+                rows.push((title.clone(), width));
+            }
+        }
+        if self.config.filterable {
+            let prompt = format!("Filter: {}", self.query);
+            let width = prompt.width();
+
+            rows.push((prompt, width));
+        }
+
+        if self.visible_items.is_empty() {
+            rows.push((self.dimmed(NO_MATCHES_TEXT), NO_MATCHES_TEXT.width()));
+        } else if self.config.horizontal {
+            let lines: Vec<&Line> = self
+                .visible_items
+                .iter()
+                .map(|&index| &self.lines[index])
+                .collect();
+            let row = lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>();
+            let width = lines.iter().map(|line| line.len()).sum::<usize>()
+                + HORIZONTAL_SEPARATOR.len() * lines.len().saturating_sub(1);
+
+            rows.push((row.join(HORIZONTAL_SEPARATOR), width));
+        }
+        if is_scrolling {
+            rows.push(scroll_indicator(self.scroll_offset > 0, "↑ more"));
+        }
+        for position in (self.scroll_offset..window_end).filter(|_| !self.config.horizontal) {
+            if let Some(label) = self.group_header_before(position) {
+                rows.push((self.bold(label), label.width()));
+            }
+            let line = &self.lines[self.visible_items[position]];
+            rows.push((line.to_string(), line.len()));
+        }
+        if is_scrolling {
+            rows.push(self.bottom_indicator());
+        }
+        if let Some(page) = self.page_indicator() {
+            let width = page.width();
+
+            rows.push((page, width));
+        }
+        if self.armed {
+            let armed_text = self.armed_text();
+            let width = armed_text.width();
+
+            rows.push((armed_text, width));
+        } else if let Some(notice) = &self.notice {
+            rows.push((notice.clone(), notice.width()));
+        } else if let Some(help_text) = &self.config.help_text {
+            rows.push((self.dimmed(help_text), help_text.width()));
+        }
+
+        if self.config.bordered {
+            rows = self.frame(rows);
+        }
+        self.printed_widths = rows.iter().map(|(_, width)| *width).collect();
+
+        if let Some(height) = self.terminal_height {
+            // the terminal scrolls up if the dialog and the cursor below it don't fit below the first row
+            let printed_rows: usize = rows
+                .iter()
+                .map(|(_, width)| self.terminal_rows(*width))
+                .sum();
+            self.first_row = self.first_row.min(height.saturating_sub(1 + printed_rows));
+        }
+
+        for (row, _) in rows {
+            write!(&mut self.out, "{}\r\n", row)?;
+        }
+        // the output may be buffered, the dialog has to be visible before waiting for input
+        self.out.flush()
+    }
+    /// Returns the text in bold, or unchanged if NO_COLOR is set
+    fn bold(&self, text: &str) -> String {
+        if self.config.no_color {
+            return text.to_string();
+        }
+        format!(
+            "{}{}{}",
+            SetAttribute(Attribute::Bold),
+            text,
+            SetAttribute(Attribute::NormalIntensity)
+        )
+    }
+    /// Returns the text dimmed, or unchanged if NO_COLOR is set
+    fn dimmed(&self, text: &str) -> String {
+        if self.config.no_color {
+            return text.to_string();
+        }
+        format!(
+            "{}{}{}",
+            SetAttribute(Attribute::Dim),
+            text,
+            SetAttribute(Attribute::NormalIntensity)
+        )
+    }
+    /// Returns the text below the items after the first confirmation in `confirm_twice` mode
+    fn armed_text(&self) -> String {
+        let key = self
+            .config
+            .key_map
+            .confirm
+            .first()
+            .map_or(String::from("enter"), key_name);
+
+        format!("Press {} again to confirm", key)
+    }
+    /// Prints the state of the dialog as a plain line, unless it is the same as the one printed last
+    fn announce(&mut self) -> std::io::Result<()> {
+        let announcement = self.announcement_text();
+
+        if self.announcement.as_ref() != Some(&announcement) {
+            write!(&mut self.out, "{}\r\n", announcement)?;
+            self.out.flush()?;
+            self.announcement = Some(announcement);
+        }
+        Ok(())
+    }
+    /// Returns the text of the accessible mode, like "Selected: item2 (2 of 5)"
+    fn announcement_text(&self) -> String {
+        if self.visible_items.is_empty() {
+            return NO_MATCHES_TEXT.to_string();
+        }
+        let mut text = format!(
+            "Selected: {} ({} of {})",
+            self.lines[self.selected_item].text(),
+            self.position_of(self.selected_item) + 1,
+            self.visible_items.len()
+        );
+        if self.config.multi_select && self.checked_items.contains(&self.selected_item) {
+            text.push_str(", checked");
+        }
+        if self.disabled_items.contains(&self.selected_item) {
+            text.push_str(", disabled");
+        }
+        if self.armed {
+            text = format!("{}\r\n{}", text, self.armed_text());
+        } else if let Some(notice) = &self.notice {
+            text = format!("{}\r\n{}", text, notice);
+        }
+        text
+    }
+    /// Returns the number of cells a line may take: the width of the terminal, limited by max_width
+    /// and reduced by the border. None if neither the terminal width nor max_width is known.
+    fn line_width(&self) -> Option<usize> {
+        let width = match (self.terminal_width, self.config.max_width) {
+            (Some(terminal_width), Some(max_width)) => Some(terminal_width.min(max_width)),
+            (terminal_width, max_width) => terminal_width.or(max_width),
+        }?;
+        if self.config.bordered {
+            Some(width.saturating_sub(BORDER_WIDTH))
+        } else {
+            Some(width)
+        }
+    }
+    /// Draws a border around the rows. The title, if set, is printed in the top border.
+    fn frame(&self, rows: Vec<(String, usize)>) -> Vec<(String, usize)> {
+        let title_width = self
+            .config
+            .title
+            .as_ref()
+            .map_or(0, |title| title.width() + 1);
+        let inner_width = rows
+            .iter()
+            .map(|(_, width)| *width)
+            .max()
+            .unwrap_or(0)
+            .max(title_width);
+        let top = match &self.config.title {
+            Some(title) if self.config.theme.bold_title => format!(
+                "┌─ {} {}┐",
+                self.bold(title),
+                "─".repeat(inner_width - title_width)
+            ),
+            Some(title) => format!("┌─ {} {}┐", title, "─".repeat(inner_width - title_width)),
+            None => format!("┌{}┐", "─".repeat(inner_width + 2)),
+        };
+        let full_width = inner_width + BORDER_WIDTH;
+        let mut framed = vec![(top, full_width)];
+
+        for (row, width) in rows {
+            framed.push((
+                format!("│ {}{} │", row, " ".repeat(inner_width - width)),
+                full_width,
+            ));
+        }
+        framed.push((format!("└{}┘", "─".repeat(inner_width + 2)), full_width));
+        framed
+    }
+    /// Resets the style of all lines and applies the styles of the selected item
+    fn style_lines(&mut self) {
+        self.lines.iter_mut().for_each(|line| line.default());
+
+        if let Some(width) = self.line_width() {
+            // lines wider than the terminal would wrap and break the erasing
+            self.lines.iter_mut().for_each(|line| line.max_width(width));
+        }
+
+        // only the previous and the new selected item are rendered differently than before
+        if let (Some(render), true) = (&self.render, self.rendered_item != self.selected_item) {
+            let previous_item = self.rendered_item;
+
+            self.lines[previous_item].set_text(render(&self.items[previous_item], false));
+            self.lines[self.selected_item].set_text(render(&self.items[self.selected_item], true));
+            self.rendered_item = self.selected_item;
+        }
+
+        if self.config.theme.dim_unselected {
+            for (index, line) in self.lines.iter_mut().enumerate() {
+                if index != self.selected_item {
+                    line.dim();
+                }
+            }
+        }
+        self.lines[self.selected_item].select();
+
+        if self.config.theme.underline_selected_item {
+            self.lines[self.selected_item].underline();
+        }
+        if self.config.theme.bold_selected_item {
+            self.lines[self.selected_item].bold();
+        }
+        if self.config.theme.reverse_selected_item {
+            self.lines[self.selected_item].reverse();
+        }
+        if self.config.theme.selected_indent > 0 {
+            self.lines[self.selected_item]
+                .space_from_pointer(1 + self.config.theme.selected_indent);
+        }
+        // NO_COLOR only turns off colors, other styles are kept
+        if !self.config.no_color {
+            if let Some(color) = self.config.theme.selected_fg {
+                self.lines[self.selected_item].foreground(color);
+            }
+            if let Some(color) = self.config.theme.selected_bg {
+                self.lines[self.selected_item].background(color);
+            }
+            if let Some(color) = self.config.theme.pointer_color {
+                self.lines[self.selected_item].pointer_color(color);
+            }
+        }
+        if self.config.multi_select {
+            for (index, line) in self.lines.iter_mut().enumerate() {
+                line.checkbox(self.checked_items.contains(&index));
+            }
+        }
+        if self.annotation.is_some() && self.line_width().is_none() {
+            // without the width of the terminal, the annotations are aligned to the widest line
+            let width = self.lines.iter().map(Line::len).max().unwrap_or(0);
+            self.lines.iter_mut().for_each(|line| line.max_width(width));
+        }
+    }
+    /// Returns the index of the printed row that shows the item at the given index
+    fn row_of_item(&self, index: usize) -> usize {
+        let is_scrolling = self.is_scrolling();
+        let position = self.position_of(index);
+        let group_headers = (self.scroll_offset..=position)
+            .filter(|&position| self.group_header_before(position).is_some())
+            .count();
+
+        self.header_rows() + is_scrolling as usize + group_headers + position - self.scroll_offset
+    }
+    /// Returns the label of the group that starts at the given position of the window.
+    /// The first item of the window always shows the header of its group, so it stays visible while scrolling.
+    fn group_header_before(&self, position: usize) -> Option<&str> {
+        let group = self.group_of(self.visible_items[position])?;
+
+        if position == self.scroll_offset
+            || self.group_of(self.visible_items[position - 1]) != Some(group)
+        {
+            Some(&self.groups[group].0)
+        } else {
+            None
+        }
+    }
+    /// Returns the number of the group that contains the item at the given index
+    fn group_of(&self, index: usize) -> Option<usize> {
+        self.groups
+            .iter()
+            .position(|(_, indices)| indices.contains(&index))
+    }
+    /// Overwrites the printed row of a single item and moves the cursor back below the list
+    fn redraw_line(&mut self, index: usize) -> std::io::Result<()> {
+        let row = self.row_of_item(index);
+        let line = &self.lines[index];
+
+        self.redraw_row(row, line.to_string(), line.len())
+    }
+    /// Overwrites a printed row with the text and moves the cursor back below the list
+    fn redraw_row(&mut self, row: usize, text: String, width: usize) -> std::io::Result<()> {
+        let distance = (self.printed_widths.len() - row) as u16;
+
+        execute!(self.out, MoveToPreviousLine(distance))?;
+        write!(
+            &mut self.out,
+            "{}\r{}",
+            " ".repeat(self.printed_widths[row]),
+            text
+        )?;
+        execute!(self.out, MoveToNextLine(distance))?;
+
+        self.printed_widths[row] = width;
+        Ok(())
+    }
+    /// Returns the row below the window that shows if more items follow, together with its width.
+    /// With the position indicator, it also shows the position of the selected item, aligned to the right.
+    fn bottom_indicator(&self) -> (String, usize) {
+        let window_end = self.scroll_offset + self.window_size.min(self.visible_items.len());
+        let (text, width) = scroll_indicator(window_end < self.visible_items.len(), "↓ more");
+
+        if !self.config.position_indicator {
+            return (text, width);
+        }
+        let counter = format!("{}/{}", self.position() + 1, self.visible_items.len());
+        let counter_width = counter.width();
+        // the last cell of the terminal is left free, writing to it could wrap the row
+        let padding = match self.line_width() {
+            Some(line_width) => line_width.saturating_sub(1 + width + counter_width),
+            None => (width > 0) as usize,
+        };
+        (
+            format!("{}{}{}", text, " ".repeat(padding), counter),
+            width + padding + counter_width,
+        )
+    }
+    /// Returns whether the window is smaller than the list and shows the scroll indicators.
+    /// Paginated lists switch between pages instead.
+    fn is_scrolling(&self) -> bool {
+        self.config.per_page.is_none() && self.window_size < self.visible_items.len()
+    }
+    /// Returns the row below a paginated list that shows the current page, for example `Page 1/4`
+    fn page_indicator(&self) -> Option<String> {
+        let per_page = self.config.per_page?;
+        let pages = self.visible_items.len().div_ceil(per_page);
+
+        (pages > 0).then(|| format!("Page {}/{}", self.position() / per_page + 1, pages))
+    }
+    /// Moves the visible window so that the selected item is part of it
+    fn scroll_to_selected_item(&mut self) {
+        let window_size = self.window_size.min(self.visible_items.len());
+        let position = self.position();
+
+        if let Some(per_page) = self.config.per_page {
+            self.scroll_offset = position - position % per_page;
+            return;
+        }
+
+        // the margin can't be kept on both sides of a small window
+        let margin = self
+            .config
+            .scroll_off
+            .min(window_size.saturating_sub(1) / 2);
+        let last_offset = self.visible_items.len() - window_size;
+
+        if position < self.scroll_offset + margin {
+            self.scroll_offset = position.saturating_sub(margin);
+        } else if position + margin >= self.scroll_offset + window_size {
+            self.scroll_offset = (position + margin + 1 - window_size).min(last_offset);
+        }
+    }
+    /// Returns the number of printed rows above the list
+    fn header_rows(&self) -> usize {
+        // with a border, the title is printed in the top border
+        (self.config.title.is_some() || self.config.bordered) as usize
+            + self.config.filterable as usize
+    }
+    /// Returns the number of printed rows below the list
+    fn footer_rows(&self) -> usize {
+        (self.config.help_text.is_some() || self.armed || self.notice.is_some()) as usize
+            + self.config.bordered as usize
+    }
+    /// Calculates how many items fit on the screen. If not all items fit, two rows are reserved for the scroll indicators.
+    /// The result is limited by max_visible.
+    fn update_window_size(&mut self) {
+        let item_count = self.items.len();
+        let terminal_size = size().ok();
+        self.terminal_width = terminal_size.map(|(width, _)| width as usize);
+        self.terminal_height = terminal_size.map(|(_, height)| height as usize);
+
+        if self.config.horizontal {
+            // all items are printed in a single row, which never scrolls
+            self.window_size = usize::MAX;
+            return;
+        }
+        if let Some(per_page) = self.config.per_page {
+            self.window_size = per_page;
+            return;
+        }
+        self.window_size = match self.config.visible_rows {
+            Some(rows) => rows.max(1),
+            None => match size() {
+                Ok((_, height)) => {
+                    // one row is needed for the cursor below the list
+                    // every group may print its header inside the window
+                    // confirm_twice and the selection limits may print a footer later
+                    let footer_rows = self.footer_rows().max(
+                        (self.config.confirm_twice
+                            || self.config.min_selections > 0
+                            || self.config.max_selections.is_some())
+                            as usize
+                            + self.config.bordered as usize,
+                    );
+                    let available = (height as usize)
+                        .saturating_sub(1 + self.header_rows() + footer_rows + self.groups.len());
+
+                    if item_count <= available {
+                        item_count
+                    } else {
+                        available.saturating_sub(2).max(1)
+                    }
+                }
+                Err(_) => item_count,
+            },
+        };
+        if let Some(max_visible) = self.config.max_visible {
+            self.window_size = self.window_size.min(max_visible.max(1));
+        }
+    }
+
+    /// Overwrites the printed lines with blanks and moves the cursor back to the first line
+    fn erase_printed_items(&mut self) -> std::io::Result<()> {
+        let widths = std::mem::take(&mut self.printed_widths);
+        let height = widths.iter().map(|width| self.terminal_rows(*width)).sum();
+        self.move_n_lines_up(height)?;
+
+        for width in &widths {
+            write!(&mut self.out, "{}\r\n", " ".repeat(*width))?;
+        }
+        self.move_n_lines_up(height)
+    }
+    /// Returns the number of terminal rows a printed row of the given width takes.
+    /// It is more than one if the terminal got narrower after printing and wrapped the row.
+    fn terminal_rows(&self, width: usize) -> usize {
+        match self.terminal_width {
+            Some(terminal_width) if terminal_width > 0 => width.div_ceil(terminal_width).max(1),
+            _ => 1,
+        }
+    }
+    /// Moves the cursor to the beginning of the line n rows above the current one
+    fn move_n_lines_up(&mut self, n: usize) -> std::io::Result<()> {
+        if n == 0 {
+            return Ok(());
+        }
+        execute!(self.out, MoveToPreviousLine(n as u16))
+    }
+
+    fn move_up(&mut self) -> std::io::Result<()> {
+        let previous_position = self
+            .position()
+            .checked_sub(1)
+            .and_then(|position| self.enabled_position(position, false));
+
+        if let Some(position) = previous_position {
+            if self.is_on_current_page(position) {
+                self.move_to_position(position)?;
+            }
+        } else if self.config.wrap_around && self.config.per_page.is_none() {
+            let last_position = self.visible_items.len().saturating_sub(1);
+
+            if let Some(position) = self.enabled_position(last_position, false) {
+                self.move_to_position(position)?;
+            }
+        }
+        Ok(())
+    }
+    /// Returns whether the position is on the page of the selected item. Without pagination every position is.
+    fn is_on_current_page(&self, position: usize) -> bool {
+        match self.config.per_page {
+            Some(per_page) => position / per_page == self.position() / per_page,
+            None => true,
+        }
+    }
+    /// Switches to the previous or the next page and highlights the item in the same row, or the nearest enabled one
+    fn switch_page(&mut self, forward: bool) -> std::io::Result<()> {
+        let per_page = self.config.per_page.unwrap_or(1);
+        let position = self.position();
+        let page_start = position - position % per_page;
+        let new_page_start = if forward {
+            page_start + per_page
+        } else {
+            match page_start.checked_sub(per_page) {
+                Some(start) => start,
+                None => return Ok(()),
+            }
+        };
+        if new_page_start >= self.visible_items.len() {
+            return Ok(());
+        }
+        let page_end = (new_page_start + per_page).min(self.visible_items.len());
+        let target = (new_page_start + position % per_page).min(page_end - 1);
+        let position = self
+            .enabled_position(target, true)
+            .filter(|position| *position < page_end)
+            .or_else(|| {
+                self.enabled_position(target, false)
+                    .filter(|position| *position >= new_page_start)
+            });
+
+        if let Some(position) = position {
+            self.move_to_position(position)?;
+        }
+        Ok(())
+    }
+    fn move_page_up(&mut self) -> std::io::Result<()> {
+        self.move_up_by(self.config.page_size)
+    }
+    fn move_page_down(&mut self) -> std::io::Result<()> {
+        self.move_down_by(self.config.page_size)
+    }
+    /// Returns the number of rows half page keys move: half of the visible rows, or of the page size
+    /// if all items are visible
+    fn half_page(&self) -> usize {
+        let rows = self.window_size.min(self.visible_items.len());
+        let rows = if rows == self.visible_items.len() {
+            self.config.page_size
+        } else {
+            rows
+        };
+        (rows / 2).max(1)
+    }
+    /// Moves the selection up by the number of rows, stopping at the first item
+    fn move_up_by(&mut self, rows: usize) -> std::io::Result<()> {
+        let target = self.position().saturating_sub(rows);
+        let position = self
+            .enabled_position(target, false)
+            .or_else(|| self.enabled_position(target, true));
+
+        if let Some(position) = position {
+            self.move_to_position(position)?;
+        }
+        Ok(())
+    }
+    /// Moves the selection down by the number of rows, stopping at the last item
+    fn move_down_by(&mut self, rows: usize) -> std::io::Result<()> {
+        let last_position = self.visible_items.len().saturating_sub(1);
+        let target = self.position().saturating_add(rows).min(last_position);
+        let position = self
+            .enabled_position(target, true)
+            .or_else(|| self.enabled_position(target, false));
+
+        if let Some(position) = position {
+            self.move_to_position(position)?;
+        }
+        Ok(())
+    }
+    fn move_down(&mut self) -> std::io::Result<()> {
+        let next_position = self.enabled_position(self.position() + 1, true);
+
+        if let Some(position) = next_position {
+            if self.is_on_current_page(position) {
+                self.move_to_position(position)?;
+            }
+        } else if self.config.wrap_around && self.config.per_page.is_none() {
+            if let Some(position) = self.enabled_position(0, true) {
+                self.move_to_position(position)?;
+            }
+        }
+        Ok(())
+    }
+    /// Returns the position of the nearest enabled item, starting at the given position in the given direction
+    fn enabled_position(&self, start: usize, forward: bool) -> Option<usize> {
+        let mut position = start;
+
+        loop {
+            let index = *self.visible_items.get(position)?;
+
+            if !self.disabled_items.contains(&index) {
+                return Some(position);
+            }
+            if forward {
+                position += 1;
+            } else {
+                position = position.checked_sub(1)?;
+            }
+        }
+    }
+    /// Highlights the first enabled item
+    fn move_to_first(&mut self) -> std::io::Result<()> {
+        if let Some(position) = self.enabled_position(0, true) {
+            self.move_to_position(position)?;
+        }
+        Ok(())
+    }
+    /// Highlights the last enabled item
+    fn move_to_last(&mut self) -> std::io::Result<()> {
+        let last_position = self.visible_items.len().saturating_sub(1);
+
+        if let Some(position) = self.enabled_position(last_position, false) {
+            self.move_to_position(position)?;
+        }
+        Ok(())
+    }
+    /// Highlights the visible item at the given position, if there is one
+    fn move_to_position(&mut self, position: usize) -> std::io::Result<()> {
+        if let Some(&index) = self.visible_items.get(position) {
+            self.move_to(index)?;
+        }
+        Ok(())
+    }
+    /// Highlights the item at the given index and redraws the lines if the selection changed
+    fn move_to(&mut self, index: usize) -> std::io::Result<()> {
+        if index == self.selected_item {
+            return Ok(());
+        }
+        let previous_item = self.selected_item;
+        let scroll_offset = self.scroll_offset;
+
+        self.selected_item = index;
+        self.scroll_to_selected_item();
+
+        // the border adapts to the widest row, so the whole dialog is printed again
+        if self.printed_widths.is_empty()
+            || self.scroll_offset != scroll_offset
+            || self.config.horizontal
+            || self.config.bordered
+        {
+            self.erase_printed_items()?;
+            self.print_lines()?;
+            return Ok(());
+        }
+        // only the previous and the new selected item change when the window does not scroll
+        self.style_lines();
+        self.redraw_line(previous_item)?;
+        self.redraw_line(index)?;
+
+        if self.config.position_indicator && self.is_scrolling() {
+            let row = self.printed_widths.len() - 1 - self.footer_rows();
+            let (text, width) = self.bottom_indicator();
+
+            self.redraw_row(row, text, width)?;
+        }
+        Ok(())
+    }
+    /// Adds typed characters to the query and removes the last one on backspace, then shows the matching items.
+    /// Returns false if the event does not edit the query.
+    fn edit_query(&mut self, event: &Event) -> std::io::Result<bool> {
+        match event {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers,
+                ..
+            }) if *modifiers == KeyModifiers::NONE || *modifiers == KeyModifiers::SHIFT => {
+                self.query.push(*c);
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            }) => {
+                self.query.pop();
+            }
+            _ => return Ok(false),
+        }
+        self.filter_items();
+        self.erase_printed_items()?;
+        self.print_lines()?;
+        Ok(true)
+    }
+    /// Returns the index of the visible item whose number was pressed, if items are numbered
+    fn number_key_index(&self, event: &Event) -> Option<usize> {
+        if !self.config.with_numbers {
+            return None;
+        }
+        match event {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(digit @ '1'..='9'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            }) => {
+                let index = *digit as usize - '1' as usize;
+                Some(index).filter(|index| {
+                    self.visible_items.contains(index) && !self.disabled_items.contains(index)
+                })
+            }
+            _ => None,
+        }
+    }
+    /// Checks the selected item if it is unchecked and unchecks it otherwise
+    /// Checking more items than max_selections is rejected with a notice below the list.
+    fn toggle_selected_item(&mut self) -> std::io::Result<()> {
+        if !self.checked_items.remove(&self.selected_item) {
+            if let Some(max) = self
+                .config
+                .max_selections
+                .filter(|max| self.checked_items.len() >= *max)
+            {
+                return self.set_notice(Some(format!("Select at most {}", item_count(max))));
+            }
+            self.checked_items.insert(self.selected_item);
+        }
+        self.erase_printed_items()?;
+        self.print_lines()?;
+        Ok(())
+    }
+    /// Removes the highlighted item from the list and highlights the next one, or the previous one at the end.
+    /// Returns false if the item is kept, because only min_items are left.
+    fn remove_selected_item(&mut self) -> std::io::Result<bool> {
+        if self.items.len() - self.removed_items.len() <= self.config.min_items {
+            self.set_notice(Some(format!(
+                "Keep at least {}",
+                item_count(self.config.min_items)
+            )))?;
+            return Ok(false);
+        }
+        let position = self.position();
+
+        self.removed_items.insert(self.selected_item);
+        self.checked_items.remove(&self.selected_item);
+        self.visible_items.remove(position);
+
+        let next_position = self.enabled_position(position, true).or_else(|| {
+            position
+                .checked_sub(1)
+                .and_then(|position| self.enabled_position(position, false))
+        });
+        if let Some(position) = next_position {
+            self.selected_item = self.visible_items[position];
+        }
+        self.erase_printed_items()?;
+        self.print_lines()?;
+        Ok(true)
+    }
+    /// Returns whether the dialog may be confirmed.
+    /// If fewer items than min_selections are checked, a notice is shown below the list instead.
+    fn may_confirm(&mut self) -> std::io::Result<bool> {
+        if !self.has_min_selections() {
+            self.set_notice(Some(format!(
+                "Select at least {}",
+                item_count(self.config.min_selections)
+            )))?;
+            return Ok(false);
+        }
+        Ok(true)
+    }
+    /// Returns false if fewer items than min_selections are checked in multi select mode
+    fn has_min_selections(&self) -> bool {
+        !self.config.multi_select || self.checked_items.len() >= self.config.min_selections
+    }
+    /// Returns whether the highlighted item can be chosen: it is visible, not disabled and not removed.
+    /// After removing an item next to only disabled ones, nothing is highlighted and the dialog can only be cancelled.
+    fn is_confirmable(&self) -> bool {
+        !self.visible_items.is_empty()
+            && !self.disabled_items.contains(&self.selected_item)
+            && !self.removed_items.contains(&self.selected_item)
+    }
+    /// Returns whether the dialog is confirmed when the timeout elapses. It is cancelled with `timeout_cancels`
+    /// and whenever the highlighted item couldn't be confirmed with enter either.
+    fn confirms_on_timeout(&self) -> bool {
+        !self.config.timeout_cancels && self.is_confirmable() && self.has_min_selections()
+    }
+    /// Returns the deadline of the timeout after the event. Key presses restart it, unless `hard_timeout` is set.
+    fn next_deadline(&self, deadline: Option<Instant>, event: &Event) -> Option<Instant> {
+        match (self.config.timeout, event) {
+            (Some(timeout), Event::Key(_)) if !self.config.hard_timeout => {
+                Some(Instant::now() + timeout)
+            }
+            _ => deadline,
+        }
+    }
+    /// Calls the handler with the key and the selected item. The key is recorded in the history, even without a handler
+    fn call_event_handler_if_supplied(&mut self, key: SelectDialogKey) -> FlowControl {
+        self.key_history.push(key);
+        self.call_event_handler(key)
+    }
+    /// Calls the handler with the key and the selected item without recording the key
+    fn call_event_handler(&mut self, key: SelectDialogKey) -> FlowControl {
+        let current_item = &self.items[self.selected_item];
+        let old_index = self.reported_item.replace(self.selected_item);
+
+        if let Some(change_handler) = self.change_handler.as_ref() {
+            change_handler(&ChangeEvent {
+                key,
+                old_index,
+                new_index: self.selected_item,
+                item: current_item,
+            });
+        }
+
+        if let Some(event_handler) = self.selection_flow.as_ref() {
+            event_handler(key, current_item)
+        } else if let Some(event_handler) = self.selection_changed.as_ref() {
+            event_handler(key, current_item);
+            FlowControl::Continue
+        } else {
+            FlowControl::Continue
+        }
+    }
+    /// Starts the Select Dialog and waits for the users input. The return is a reference to the chosen item
+    ///
+    /// Pressing escape or Ctrl+C cancels the dialog. In that case `None` is returned and the cursor is left below the list.
+    /// Because the terminal is in raw mode, Ctrl+C does not raise SIGINT while the dialog is open.
+    ///
+    /// Fails with `SelectError::EmptyList` if there are no items to select from,
+    /// with `SelectError::ConflictingKeyBinding` if a key is bound to more than one action
+    /// and with `SelectError::NotATty` if the input is not a terminal, unless `headless` is set.
+    /// If reading the input or writing the output fails, `SelectError::Io` is returned after the terminal was restored.
+    pub fn start(&mut self) -> Result<Option<&I>, SelectError> {
+        if !self.run()? {
+            return Ok(None);
+        }
+        Ok(Some(&self.items[self.selected_item]))
+    }
+    /// Starts the Select Dialog like `start`, but waits for the users input without blocking the thread.
+    /// This lets the dialog run inside an async runtime like tokio.
+    ///
+    /// The timeout and the throttle work like in `start`. They don't depend on a specific runtime,
+    /// a thread wakes the dialog up and stops as soon as an event arrives first.
+    #[cfg(feature = "async")]
+    pub async fn start_async(&mut self) -> Result<Option<&I>, SelectError> {
+        if !self.run_async().await? {
+            return Ok(None);
+        }
+        Ok(Some(&self.items[self.selected_item]))
+    }
+    #[cfg(feature = "async")]
+    async fn run_async(&mut self) -> Result<bool, SelectError> {
+        self.load_items().await?;
+
+        if self.submit_without_dialog()? {
+            return Ok(true);
+        }
+        self.prepare()?;
+
+        let confirmed = {
+            let _raw_mode = self.event_source.is_none().then(RawMode::enable);
+            let modes = self.enter_terminal_modes()?;
+
+            let confirmed = self.interact_async().await;
+            self.leave_terminal_modes(modes, confirmed)?
+        };
+        self.confirm(confirmed)?;
+        Ok(confirmed)
+    }
+    /// Prints a spinner until the items of `from_future` are loaded and erases it afterwards
+    #[cfg(feature = "async")]
+    async fn load_items(&mut self) -> std::io::Result<()> {
+        let Some(mut loading) = self.loading.take() else {
+            return Ok(());
+        };
+        if self.config.accessible {
+            write!(&mut self.out, "{}\r\n", LOADING_TEXT)?;
+            self.out.flush()?;
+            let items = loading.await;
+            self.set_loaded_items(items);
+            return Ok(());
+        }
+        // the thread of the ticks stops when they are dropped once the items are loaded
+        let mut ticks = Ticks::every(SPINNER_INTERVAL);
+        let mut frame = 0;
+
+        loop {
+            self.erase_printed_items()?;
+
+            let row = format!(
+                "{} {}",
+                SPINNER_FRAMES[frame % SPINNER_FRAMES.len()],
+                LOADING_TEXT
+            );
+            self.printed_widths = vec![row.width()];
+            write!(&mut self.out, "{}\r\n", row)?;
+            self.out.flush()?;
+
+            match futures::future::select(loading, ticks.next()).await {
+                Either::Left((items, _)) => {
+                    self.erase_printed_items()?;
+                    self.set_loaded_items(items);
+                    return Ok(());
+                }
+                Either::Right((_, pending)) => {
+                    loading = pending;
+                    frame += 1;
+                }
+            }
+        }
+    }
+    /// Stores the items of `from_future` and highlights the initial item that was set while they were loading
+    #[cfg(feature = "async")]
+    fn set_loaded_items(&mut self, items: Vec<I>) {
+        self.items = Items::Owned(items);
+
+        if let Some(initial_lookup) = self.initial_lookup.take() {
+            let index = initial_lookup(&self.items);
+            self.initial_index(index);
+        }
+    }
+    #[cfg(feature = "async")]
+    async fn interact_async(&mut self) -> Result<bool, SelectError> {
+        if self.event_source.is_some() {
+            // the events of a source are read like without async
+            return self.interact();
+        }
+        if let Some(confirmed) = self.begin()? {
+            return Ok(confirmed);
+        }
+        let mut events = EventStream::new();
+        let mut deadline = self.config.timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let mut next = None;
+
+            if let (Some(throttle), Some(_)) = (self.config.throttle, self.pending_key) {
+                // the handler is only called once no further input arrives
+                match within(events.next(), throttle).await {
+                    Some(event) => next = Some(event),
+                    None => {
+                        if let Some(confirmed) = self.call_pending_handler() {
+                            return Ok(confirmed);
+                        }
+                        continue;
+                    }
+                }
+            }
+            let next = match (next, deadline) {
+                (Some(next), _) => next,
+                (None, Some(deadline)) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+
+                    match within(events.next(), remaining).await {
+                        Some(next) => next,
+                        None => {
+                            self.timed_out = true;
+                            return Ok(self.confirms_on_timeout());
+                        }
+                    }
+                }
+                (None, None) => events.next().await,
+            };
+            // the stream ends if the input is closed
+            let Some(event) = next else {
+                return Ok(false);
+            };
+            let event = event?;
+            deadline = self.next_deadline(deadline, &event);
+
+            if let Some(confirmed) = self.handle_event(&event)? {
+                return Ok(confirmed);
+            }
+        }
+    }
+    /// Starts the Select Dialog in multi select mode and waits for the users input.
+    /// The return contains references to all checked items in the order of the item list.
+    ///
+    /// Pressing escape or Ctrl+C cancels the dialog and returns no items.
+    ///
+    /// Fails with `SelectError::EmptyList` if there are no items to select from
+    /// and with `SelectError::NotATty` if the input is not a terminal.
+    /// If reading the input or writing the output fails, `SelectError::Io` is returned after the terminal was restored.
+    pub fn start_multi(&mut self) -> Result<Vec<&I>, SelectError> {
+        self.config.multi_select = true;
+
+        if !self.run()? {
+            return Ok(vec![]);
+        }
+        let mut checked_items: Vec<usize> = self.checked_items.iter().copied().collect();
+        checked_items.sort_unstable();
+
+        let items = &self.items;
+
+        Ok(checked_items
+            .into_iter()
+            .map(|index| &items[index])
+            .collect())
+    }
+    /// Starts the Select Dialog in editable mode, where delete and backspace remove the highlighted item.
+    /// The return contains the remaining items and the chosen item, which is None if the dialog was cancelled.
+    ///
+    /// Removed items are hidden rather than taken out of the items, so indices and the references of the handlers
+    /// stay valid. After a removal, the handler is called with `SelectDialogKey::DeleteKey` and the item highlighted next.
+    pub fn start_editable(&mut self) -> Result<EditedList<'_, I>, SelectError> {
+        self.editable();
+
+        let confirmed = self.run()?;
+        let items = &self.items;
+        let removed_items = &self.removed_items;
+
+        Ok(EditedList {
+            remaining: items
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !removed_items.contains(index))
+                .map(|(_, item)| item)
+                .collect(),
+            chosen: confirmed.then(|| &items[self.selected_item]),
+        })
+    }
+    /// Returns the keys the user pressed in the last dialog, in the order they were pressed.
+    /// It starts with `SelectDialogKey::Initial` and ends with `SelectDialogKey::Confirm` if the dialog was confirmed.
+    pub fn key_history(&self) -> &[SelectDialogKey] {
+        &self.key_history
+    }
+    /// Returns true if the dialog ended because the timeout elapsed, whether it was confirmed or cancelled then
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+    /// Returns the writer the dialog is printed to
+    pub fn writer(&self) -> &W {
+        &self.out
+    }
+    /// Returns the index of the highlighted item. After `start` returns, this is the index of the chosen item
+    pub fn selected_index(&self) -> usize {
+        self.selected_item
+    }
+    /// Returns the highlighted item. Before `start`, this is the item at the initial index.
+    /// Returns `None` if there are no items.
+    pub fn selected(&self) -> Option<&I> {
+        self.items.get(self.selected_item)
+    }
+    /// Returns all items of the dialog
+    pub fn items(&self) -> &[I] {
+        &self.items
+    }
+    /// Prints the dialog and handles the users input until the dialog is confirmed or cancelled.
+    /// Returns false if the dialog was cancelled.
+    fn run(&mut self) -> Result<bool, SelectError> {
+        #[cfg(feature = "async")]
+        futures::executor::block_on(self.load_items())?;
+
+        if self.submit_without_dialog()? {
+            return Ok(true);
+        }
+        self.prepare()?;
+
+        let confirmed = {
+            let _raw_mode = self.event_source.is_none().then(RawMode::enable);
+            let modes = self.enter_terminal_modes()?;
+
+            let confirmed = self.interact();
+            self.leave_terminal_modes(modes, confirmed)?
+        };
+        self.confirm(confirmed)?;
+        Ok(confirmed)
+    }
+    /// Switches on mouse capture and the alternate screen if they are set, unless the input is read from an event source
+    fn enter_terminal_modes(&mut self) -> std::io::Result<TerminalModes> {
+        let terminal = self.event_source.is_none() && !self.config.accessible;
+
+        TerminalModes::enter(
+            &mut self.out,
+            terminal && self.config.mouse,
+            terminal && self.config.alternate_screen,
+        )
+    }
+    /// Cleans up the dialog after the interaction and switches the modes off again.
+    /// The modes are left even if the interaction failed, so the terminal is usable again.
+    fn leave_terminal_modes(
+        &mut self,
+        modes: TerminalModes,
+        confirmed: Result<bool, SelectError>,
+    ) -> Result<bool, SelectError> {
+        let confirmed = confirmed.and_then(|confirmed| {
+            self.finish(confirmed)?;
+            Ok(confirmed)
+        });
+        let left = modes.leave(&mut self.out);
+        let confirmed = confirmed?;
+        left?;
+        Ok(confirmed)
+    }
+    /// Confirms an item without printing the dialog: the only item if `submit_on_single_item` is set,
+    /// or the headless item if the input is not a terminal. Returns whether an item was confirmed.
+    fn submit_without_dialog(&mut self) -> Result<bool, SelectError> {
+        let single_item = self.config.submit_on_single_item
+            && self.items.len() == 1
+            && !self.disabled_items.contains(&0);
+        let index = if single_item {
+            0
+        } else if let Some(index) =
+            self.headless_index(self.event_source.is_some() || stdin().is_tty())
+        {
+            index
+        } else {
+            return Ok(false);
+        };
+        self.selected_item = index;
+
+        if self.config.multi_select {
+            self.checked_items.insert(index);
+        }
+        self.confirm(true)?;
+        Ok(true)
+    }
+    /// Returns the item that is chosen without a dialog if the input is no terminal and headless is set
+    fn headless_index(&self, is_tty: bool) -> Option<usize> {
+        if is_tty || self.items.is_empty() {
+            return None;
+        }
+        self.config
+            .headless
+            .map(|index| index.min(self.items.len() - 1))
+    }
+    /// Checks that the dialog can be started and reads the environment
+    fn prepare(&mut self) -> Result<(), SelectError> {
+        if self.items.is_empty() {
+            return Err(SelectError::EmptyList);
+        }
+        if let Some(key) = self.config.key_map.conflicting_key(self.config.editable) {
+            return Err(SelectError::ConflictingKeyBinding(key));
+        }
+        if self.event_source.is_none() && !stdin().is_tty() {
+            return Err(SelectError::NotATty);
+        }
+        if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            self.config.no_color = true;
+        }
+        Ok(())
+    }
+    /// Tells the handler about the chosen item. The terminal is back in its normal mode, so the handler can print
+    fn confirm(&mut self, confirmed: bool) -> Result<(), SelectError> {
+        if confirmed {
+            self.call_event_handler_if_supplied(SelectDialogKey::Confirm);
+            #[cfg(feature = "serde")]
+            self.write_json()?;
+        }
+        Ok(())
+    }
+    /// Writes a JSON line with index and item of every chosen item to the writer set with `json_output`
+    #[cfg(feature = "serde")]
+    fn write_json(&mut self) -> Result<(), SelectError> {
+        let Some(json_output) = self.json_output.as_mut() else {
+            return Ok(());
+        };
+        let mut chosen: Vec<usize> = if self.config.multi_select {
+            self.checked_items.iter().copied().collect()
+        } else {
+            vec![self.selected_item]
+        };
+        chosen.sort_unstable();
+
+        for index in chosen {
+            let item = (json_output.serialize)(&self.items[index]).map_err(std::io::Error::from)?;
+            writeln!(json_output.out, "{{\"index\":{},\"item\":{}}}", index, item)?;
+        }
+        json_output.out.flush()?;
+        Ok(())
+    }
+    /// Cleans up the printed dialog after it was confirmed or cancelled
+    fn finish(&mut self, confirmed: bool) -> std::io::Result<()> {
+        if self.config.clear_on_exit || self.config.report_prefix.is_some() {
+            self.erase_printed_items()?;
+        }
+        let prefix = match &self.config.report_prefix {
+            Some(prefix) => Some(prefix.as_str()),
+            None if self.config.accessible => Some("Confirmed:"),
+            None => None,
+        };
+        if let (true, Some(prefix)) = (confirmed, prefix) {
+            let lines = &self.lines;
+            let chosen: Vec<&str> = if self.config.multi_select {
+                let mut checked_items: Vec<usize> = self.checked_items.iter().copied().collect();
+                checked_items.sort_unstable();
+
+                checked_items
+                    .into_iter()
+                    .map(|index| lines[index].text())
+                    .collect()
+            } else {
+                vec![lines[self.selected_item].text()]
+            };
+            write!(&mut self.out, "{} {}\r\n", prefix, chosen.join(", "))?;
+        }
+        self.out.flush()
+    }
+    /// Prints the dialog the first time. Returns whether the dialog was confirmed if the handler already ended it.
+    fn begin(&mut self) -> std::io::Result<Option<bool>> {
+        self.armed = false;
+        self.notice = None;
+        self.pending_key = None;
+        self.reported_item = None;
+        self.build_lines();
+        self.update_window_size();
+
+        // rows of scripted mouse events count from the first row of the dialog
+        self.first_row = 0;
+
+        if self.config.mouse && self.event_source.is_none() {
+            if let Ok((_, row)) = cursor::position() {
+                self.first_row = row as usize;
+            }
+        }
+        if self.config.accessible {
+            self.announcement = None;
+
+            if let Some(title) = &self.config.title {
+                write!(&mut self.out, "{}\r\n", title)?;
+            }
+        }
+        self.print_lines()?;
+
+        self.timed_out = false;
+        self.key_history.clear();
+
+        if !self.visible_items.is_empty() {
+            if let Some(confirmed) = self.flow_result(SelectDialogKey::Initial) {
+                return Ok(Some(confirmed));
+            }
+        }
+        Ok(None)
+    }
+    /// Prints the dialog and waits for the users input. Returns false if the dialog was cancelled.
+    fn interact(&mut self) -> Result<bool, SelectError> {
+        if let Some(confirmed) = self.begin()? {
+            return Ok(confirmed);
+        }
+        let mut deadline = self.config.timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            if let (Some(throttle), Some(_)) = (self.config.throttle, self.pending_key) {
+                // the handler is only called once no further input arrives
+                if !self.poll_event(throttle)? {
+                    if let Some(confirmed) = self.call_pending_handler() {
+                        return Ok(confirmed);
+                    }
+                    continue;
+                }
+            }
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+
+                if !self.poll_event(remaining)? {
+                    self.timed_out = true;
+                    return Ok(self.confirms_on_timeout());
+                }
+            }
+            let event = self.read_event()?;
+            deadline = self.next_deadline(deadline, &event);
+
+            if let Some(confirmed) = self.handle_event(&event)? {
+                return Ok(confirmed);
+            }
+        }
+    }
+    /// Returns whether an event can be read within the timeout, from the event source if one is set
+    fn poll_event(&mut self, timeout: Duration) -> std::io::Result<bool> {
+        match self.event_source.as_mut() {
+            Some(event_source) => event_source.poll(timeout),
+            None => TerminalEvents.poll(timeout),
+        }
+    }
+    /// Reads the next event, from the event source if one is set
+    fn read_event(&mut self) -> std::io::Result<Event> {
+        match self.event_source.as_mut() {
+            Some(event_source) => event_source.read(),
+            None => TerminalEvents.read(),
+        }
+    }
+    /// Reacts to a single event of the user. Returns whether the dialog was confirmed if the event ended it.
+    fn handle_event(&mut self, event: &Event) -> std::io::Result<Option<bool>> {
+        // Windows reports the release of every key as well, only presses count
+        if let Event::Key(KeyEvent { kind, .. }) = event {
+            if *kind != KeyEventKind::Press {
+                return Ok(None);
+            }
+        }
+        if let Event::Resize(..) = event {
+            self.update_window_size();
+            self.erase_printed_items()?;
+            self.print_lines()?;
+            return Ok(None);
+        }
+        if self.event_contains_key(event, &self.config.key_map.confirm) {
+            if !self.is_confirmable() {
+                return Ok(None);
+            }
+            if !self.may_confirm()? {
+                return Ok(None);
+            }
+            if self.config.confirm_twice && !self.armed {
+                self.set_armed(true)?;
+                return Ok(None);
+            }
+            return Ok(Some(true));
+        }
+        if self.armed && matches!(event, Event::Key(_)) {
+            self.set_armed(false)?;
+        }
+        if self.notice.is_some() && matches!(event, Event::Key(_)) {
+            self.set_notice(None)?;
+        }
+        if self.event_is_one_of(event, &self.config.key_map.cancel) {
+            return Ok(Some(false));
+        }
+        if self.config.filterable && self.edit_query(event)? {
+            return Ok(None);
+        }
+        if self.visible_items.is_empty() {
+            // nothing can be selected until the filter matches again
+            return Ok(None);
+        }
+        if self.config.editable && self.event_is_one_of(event, &self.config.key_map.remove) {
+            if !self.remove_selected_item()? || !self.is_confirmable() {
+                return Ok(None);
+            }
+            return Ok(self.flow_result(SelectDialogKey::DeleteKey));
+        }
+        if let Some(index) = self.number_key_index(event) {
+            if index == self.selected_item || self.config.number_selects_immediately {
+                self.move_to(index)?;
+                return Ok(self.may_confirm()?.then_some(true));
+            }
+            self.move_to(index)?;
+
+            return Ok(self.flow_result(SelectDialogKey::NumberKey));
+        }
+        if let Some(index) = self.clicked_item(event) {
+            if index == self.selected_item {
+                return Ok(self.may_confirm()?.then_some(true));
+            }
+            self.move_to(index)?;
+
+            return Ok(self.flow_result(SelectDialogKey::MouseClick));
+        }
+        if self.config.multi_select
+            && *event == Event::Key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE))
+        {
+            self.toggle_selected_item()?;
+            return Ok(None);
+        }
+        if let Some(key) = self.navigate(event)? {
+            return Ok(self.flow_result(key));
+        }
+        if let Some(index) = self.type_ahead_index(event) {
+            self.move_to(index)?;
+
+            return Ok(self.flow_result(SelectDialogKey::TypeAhead));
+        }
+        self.handle_unhandled_key(event)
+    }
+    /// Passes a key the dialog doesn't use to the handler set with `on_unhandled_key`.
+    /// Returns whether the dialog was confirmed if the handler ended it.
+    fn handle_unhandled_key(&mut self, event: &Event) -> std::io::Result<Option<bool>> {
+        let (Event::Key(key_event), Some(handler)) = (event, &self.unhandled_key) else {
+            return Ok(None);
+        };
+        match handler(*key_event) {
+            FlowControl::Continue => Ok(None),
+            FlowControl::Cancel => Ok(Some(false)),
+            FlowControl::Confirm => {
+                Ok((self.is_confirmable() && self.may_confirm()?).then_some(true))
+            }
+        }
+    }
+    /// Returns the index of the next item after the selected one that starts with the typed letter, ignoring case.
+    /// The search continues at the top of the list, so pressing the letter again cycles through the matching items.
+    fn type_ahead_index(&self, event: &Event) -> Option<usize> {
+        let typed = match event {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers,
+                ..
+            }) if self.config.type_ahead
+                && (*modifiers == KeyModifiers::NONE || *modifiers == KeyModifiers::SHIFT) =>
+            {
+                c.to_lowercase().to_string()
+            }
+            _ => return None,
+        };
+        let position = self.position();
+        let count = self.visible_items.len();
+
+        (1..=count)
+            .map(|offset| self.visible_items[(position + offset) % count])
+            .find(|index| {
+                !self.disabled_items.contains(index)
+                    && self.lines[*index].text().to_lowercase().starts_with(&typed)
+            })
+    }
+    /// Shows or hides the request to confirm again
+    fn set_armed(&mut self, armed: bool) -> std::io::Result<()> {
+        self.armed = armed;
+        self.erase_printed_items()?;
+        self.print_lines()?;
+        Ok(())
+    }
+    /// Shows or hides a message below the list, which replaces the help text until the next key
+    fn set_notice(&mut self, notice: Option<String>) -> std::io::Result<()> {
+        self.notice = notice;
+        self.erase_printed_items()?;
+        self.print_lines()?;
+        Ok(())
+    }
+    /// Calls the handler and returns whether the dialog was confirmed if the handler ended it.
+    /// With a throttle, the key is recorded and the handler is called later by `call_pending_handler`.
+    fn flow_result(&mut self, key: SelectDialogKey) -> Option<bool> {
+        if self.config.throttle.is_some() && key != SelectDialogKey::Initial {
+            self.key_history.push(key);
+            self.pending_key = Some(key);
+            return None;
+        }
+        match self.call_event_handler_if_supplied(key) {
+            FlowControl::Continue => None,
+            flow => Some(flow == FlowControl::Confirm),
+        }
+    }
+    /// Calls the handler with the last key that was held back by the throttle, if there is one
+    fn call_pending_handler(&mut self) -> Option<bool> {
+        let key = self.pending_key.take()?;
+
+        match self.call_event_handler(key) {
+            FlowControl::Continue => None,
+            flow => Some(flow == FlowControl::Confirm),
+        }
+    }
+    /// Moves the selection if the event is a navigation key and returns which one was pressed
+    fn navigate(&mut self, event: &Event) -> std::io::Result<Option<SelectDialogKey>> {
+        if self.config.per_page.is_some() {
+            if self.event_is_one_of(event, &self.config.key_map.page_up)
+                || *event == Event::Key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE))
+            {
+                self.switch_page(false)?;
+                return Ok(Some(SelectDialogKey::PageUpKey));
+            }
+            if self.event_is_one_of(event, &self.config.key_map.page_down)
+                || *event == Event::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE))
+            {
+                self.switch_page(true)?;
+                return Ok(Some(SelectDialogKey::PageDownKey));
+            }
+        }
+        if self.event_is_one_of(event, &self.config.key_map.page_up) {
+            self.move_page_up()?;
+            Ok(Some(SelectDialogKey::PageUpKey))
+        } else if self.event_is_one_of(event, &self.config.key_map.page_down) {
+            self.move_page_down()?;
+            Ok(Some(SelectDialogKey::PageDownKey))
+        } else if self.event_is_one_of(event, &self.config.key_map.half_page_up) {
+            self.move_up_by(self.half_page())?;
+            Ok(Some(SelectDialogKey::HalfPageUpKey))
+        } else if self.event_is_one_of(event, &self.config.key_map.half_page_down) {
+            self.move_down_by(self.half_page())?;
+            Ok(Some(SelectDialogKey::HalfPageDownKey))
+        } else if self.event_is_one_of(event, &self.config.key_map.home) {
+            self.move_to_first()?;
+            Ok(Some(SelectDialogKey::HomeKey))
+        } else if self.event_is_one_of(event, &self.config.key_map.end) {
+            self.move_to_last()?;
+            Ok(Some(SelectDialogKey::EndKey))
+        } else if self.event_contains_key(event, &self.config.key_map.up)
+            || (self.config.horizontal
+                && *event == Event::Key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE)))
+            || matches!(
+                event,
+                Event::Mouse(MouseEvent {
+                    kind: MouseEventKind::ScrollUp,
+                    ..
+                })
+            )
+        {
+            self.move_up()?;
+            Ok(Some(SelectDialogKey::UpKey))
+        } else if self.event_contains_key(event, &self.config.key_map.down)
+            || (self.config.horizontal
+                && *event == Event::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE)))
+            || matches!(
+                event,
+                Event::Mouse(MouseEvent {
+                    kind: MouseEventKind::ScrollDown,
+                    ..
+                })
+            )
+        {
+            self.move_down()?;
+            Ok(Some(SelectDialogKey::DownKey))
+        } else {
+            Ok(None)
+        }
+    }
+    /// Returns the index of the item that was clicked with the left mouse button, unless it is disabled
+    fn clicked_item(&self, event: &Event) -> Option<usize> {
+        let mouse_event = match event {
+            Event::Mouse(mouse_event)
+                if mouse_event.kind == MouseEventKind::Down(MouseButton::Left)
+                    && !self.config.horizontal =>
+            {
+                mouse_event
+            }
+            _ => return None,
+        };
+        let row = (mouse_event.row as usize).checked_sub(self.first_row)?;
+
+        self.item_at_row(row)
+            .filter(|index| !self.disabled_items.contains(index))
+    }
+    /// Returns the index of the item that is printed in the given row of the dialog
+    fn item_at_row(&self, row: usize) -> Option<usize> {
+        let window_end =
+            (self.scroll_offset.saturating_add(self.window_size)).min(self.visible_items.len());
+
+        self.visible_items[self.scroll_offset..window_end]
+            .iter()
+            .copied()
+            .find(|&index| self.row_of_item(index) == row)
+    }
+    /// Checks if the event is a press of one of the keys, letters only match in the same case
+    fn event_is_one_of(&self, event: &Event, keys: &[KeyEvent]) -> bool {
+        matches!(event, Event::Key(key_event) if keys.contains(key_event))
+    }
+    /// Checks if the event is a press of one of the keys. Letters match regardless of their case,
+    /// so a key bound to `j` also reacts to `J` when shift or caps lock is active.
+    /// Back tab matches with or without shift.
+    fn event_contains_key(&self, event: &Event, keys: &[KeyEvent]) -> bool {
+        for key in keys.iter() {
+            if *event == Event::Key(*key) {
+                return true;
+            }
+            if let (Event::Key(key_event), KeyCode::Char(c)) = (event, key.code) {
+                let lowercase_event =
+                    KeyEvent::new(KeyCode::Char(c.to_ascii_lowercase()), key.modifiers);
+                let uppercase_event =
+                    KeyEvent::new(KeyCode::Char(c.to_ascii_uppercase()), key.modifiers);
+
+                if *key_event == lowercase_event || *key_event == uppercase_event {
+                    return true;
+                }
+            }
+            // terminals send back tab together with shift, which is already implied by the key
+            if let (Event::Key(key_event), KeyCode::BackTab) = (event, key.code) {
+                if *key_event
+                    == KeyEvent::new(KeyCode::BackTab, key.modifiers | KeyModifiers::SHIFT)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    /// Set the item that is highlighted when the dialog is printed the first time.
+    /// An index out of range highlights the last item.
+    /// For `from_future`, the index is applied once the items are loaded.
+    pub fn initial_index(&mut self, index: usize) -> &mut Self {
+        #[cfg(feature = "async")]
+        if self.loading.is_some() {
+            self.initial_lookup = Some(Box::new(move |_| index));
+            return self;
+        }
+        self.initial_item = index.min(self.items.len().saturating_sub(1));
+        self.selected_item = self.initial_item;
+        self
+    }
+    /// Set the dialog back to the state before it was started the first time, so it can be started again from scratch:
+    /// the initial item is highlighted, the filter is empty and no item is checked.
+    ///
+    /// Without a reset, `start` can be called again as well. The dialog is printed anew below the previous one
+    /// and the last chosen item stays highlighted, which is handy to prompt repeatedly in a loop.
+    pub fn reset(&mut self) -> &mut Self {
+        self.selected_item = self.initial_item;
+        self.query.clear();
+        self.checked_items.clear();
+        self.removed_items.clear();
+        self.scroll_offset = 0;
+        self.printed_widths.clear();
+        self.timed_out = false;
+        self.key_history.clear();
+        self
+    }
+    /// Let delete and backspace remove the highlighted item from the list. `start_editable` enables it
+    /// and returns the remaining items as well.
+    pub fn editable(&mut self) -> &mut Self {
+        self.config.editable();
+        self
+    }
+    /// Keep at least the given number of items in editable mode. Removing another one is rejected with a notice.
+    /// Default is 1, the last item is never removed.
+    pub fn min_items(&mut self, count: usize) -> &mut Self {
+        self.config.min_items(count);
+        self
+    }
+    /// Show a filter prompt above the items. Typed characters are added to the filter and only the items
+    /// that contain it are shown, ignoring case. Backspace removes the last character.
+    ///
+    /// In this mode, characters are used for the filter instead of custom up and down keys.
+    ///
+    /// If no item matches, `No matches` is printed instead and enter does nothing until backspace brings items back.
+    /// The highlighted item stays the same while nothing matches.
+    pub fn filterable(&mut self) -> &mut Self {
+        self.config.filterable();
+        self
+    }
+    /// Set a handler that is called every time the user moves the selection.
+    /// It is also called once with `SelectDialogKey::Initial` after the dialog is printed the first time.
+    ///
+    /// When the dialog is confirmed, it is called a last time with `SelectDialogKey::Confirm` and the chosen item.
+    /// This happens after the dialog stopped waiting for input and the terminal left raw mode, right before `start` returns.
+    pub fn on_selection_changed(
+        &mut self,
+        handler: impl Fn(SelectDialogKey, &I) + 'static,
+    ) -> &mut Self {
+        self.selection_changed = Some(Box::new(handler));
+        self
+    }
+    /// Set a handler that is called with a `ChangeEvent` every time the user moves the selection, like the one of
+    /// `on_selection_changed`. Besides the key and the item, the event carries the index of the item before and after the change.
+    ///
+    /// It is called in addition to a handler set with `on_selection_changed` or `on_selection_changed_flow`.
+    pub fn on_change(&mut self, handler: impl Fn(&ChangeEvent<I>) + 'static) -> &mut Self {
+        self.change_handler = Some(Box::new(handler));
+        self
+    }
+    /// Set a handler that is called every time the user moves the selection and decides how the dialog continues.
+    /// Returning `FlowControl::Confirm` chooses the current item without the need to press enter.
+    ///
+    /// It replaces a handler set with `on_selection_changed`. It is also called with `SelectDialogKey::Confirm`, but its result is ignored then.
+    pub fn on_selection_changed_flow(
+        &mut self,
+        handler: impl Fn(SelectDialogKey, &I) -> FlowControl + 'static,
+    ) -> &mut Self {
+        self.selection_flow = Some(Box::new(handler));
+        self
+    }
+    /// Read the input from the given source instead of the terminal, for example scripted keys with `VecEventSource`
+    /// to test a dialog end to end. The input doesn't have to be a terminal then and the terminal is left as it is:
+    /// no raw mode, mouse capture or alternate screen. `start_async` reads the source without async.
+    ///
+    /// The rows of mouse events from the source count from the first row of the dialog.
+    pub fn event_source(&mut self, event_source: impl EventSource + 'a) -> &mut Self {
+        self.event_source = Some(Box::new(event_source));
+        self
+    }
+    /// Set a handler for keys the dialog doesn't use itself, to add own shortcuts like a help popup.
+    /// Navigation, confirm and cancel keys and characters typed into the filter don't reach it.
+    ///
+    /// Returning `FlowControl::Cancel` stops the dialog without a chosen item and `FlowControl::Confirm` chooses the selected item.
+    pub fn on_unhandled_key(
+        &mut self,
+        handler: impl Fn(KeyEvent) -> FlowControl + 'static,
+    ) -> &mut Self {
+        self.unhandled_key = Some(Box::new(handler));
+        self
+    }
+    /// Set a function that creates the text of an item instead of `to_string`, for example to show a single field of a struct.
+    pub fn label_fn(&mut self, label: impl Fn(&I) -> String + 'a) -> &mut Self {
+        self.label = Box::new(label);
+        self
+    }
+    /// Set a function that creates a secondary text for every item, that is printed dimmed and aligned to the right,
+    /// like the shortcuts in a command palette. If space is tight, the text of the item is cut instead.
+    /// Items with an empty annotation don't show one.
+    pub fn annotation_fn(&mut self, annotation: impl Fn(&I) -> String + 'a) -> &mut Self {
+        self.annotation = Some(Box::new(annotation));
+        self
+    }
+    /// Set a function that creates the text of an item instead of `to_string`.
+    /// The second parameter is true if the item is the selected one.
+    pub fn render_with(&mut self, render: impl Fn(&I, bool) -> String + 'a) -> &mut Self {
+        self.render = Some(Box::new(render));
+        self
+    }
+    /// Draw a border around the dialog. The title, if set, is printed in the top border.
+    pub fn bordered(&mut self) -> &mut Self {
+        self.config.bordered();
+        self
+    }
+    /// Announce the selection for screen readers: instead of redrawing the dialog in place, a plain line like
+    /// "Selected: item2 (2 of 5)" is printed whenever the selection changes and when it is confirmed.
+    /// Nothing is styled and the cursor is not moved; mouse capture and the alternate screen are not used.
+    pub fn accessible(&mut self) -> &mut Self {
+        self.config.accessible();
+        self
+    }
+    /// Limit the width of the dialog, including the border. Longer items are cut with an ellipsis.
+    pub fn max_width(&mut self, width: usize) -> &mut Self {
+        self.config.max_width(width);
+        self
+    }
+    /// Print the number of every item in front of it. Pressing the number of an item from 1 to 9 highlights it,
+    /// pressing it again confirms it.
+    pub fn with_numbers(&mut self) -> &mut Self {
+        self.config.with_numbers();
+        self
+    }
+    /// Confirm an item as soon as its number is pressed. Enables `with_numbers`.
+    pub fn number_selects_immediately(&mut self) -> &mut Self {
+        self.config.number_selects_immediately();
+        self
+    }
+    /// Confirm the highlighted item if the user does not press a key for the given duration.
+    /// Use `timed_out` to find out whether the dialog was confirmed by the user or by the timeout.
+    ///
+    /// The timeout confirms only what enter could confirm. If the highlighted item is disabled, the filter matches
+    /// nothing or fewer than `min_selections` items are checked, the dialog is cancelled instead.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.config.timeout(timeout);
+        self
+    }
+    /// Only call the selection handler once no key was pressed for the given duration, with the last key.
+    /// While a key is held, the selection moves without calling an expensive handler, for example one that loads a preview.
+    /// Confirming calls the handler with `SelectDialogKey::Confirm` as before, a held back key is dropped then.
+    pub fn throttle(&mut self, duration: Duration) -> &mut Self {
+        self.config.throttle(duration);
+        self
+    }
+    /// Cancel the dialog instead of confirming the highlighted item when the timeout elapses, so `start` returns None
+    pub fn timeout_cancels(&mut self) -> &mut Self {
+        self.config.timeout_cancels();
+        self
+    }
+    /// Don't restart the timeout on every key press, so the dialog is confirmed once the timeout elapsed after it started
+    pub fn hard_timeout(&mut self) -> &mut Self {
+        self.config.hard_timeout();
+        self
+    }
+    /// Mark the items at the given indices as disabled. They are printed dimmed and can't be selected, navigation skips them.
+    pub fn disabled_indices(&mut self, indices: &[usize]) -> &mut Self {
+        self.disabled_items.extend(indices);
+        self
+    }
+    /// Put the items at the given indices into a group. A bold header with the label is printed above them,
+    /// which can't be selected. While scrolling, the header of the top item's group stays visible.
+    pub fn group(&mut self, label: impl Into<String>, items: Range<usize>) -> &mut Self {
+        self.groups.push((label.into(), items));
+        self
+    }
+    /// Set a title that is printed above the items
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Self {
+        self.config.title(title);
+        self
+    }
+    /// Set a hint that is printed dimmed below the items, for example to explain the keys
+    pub fn help_text(&mut self, help_text: impl Into<String>) -> &mut Self {
+        self.config.help_text(help_text);
+        self
+    }
+    /// Require to press enter twice to confirm, for example before a destructive action.
+    /// After the first press, the help text is replaced by a request to press it again. Any other key cancels it.
+    pub fn confirm_twice(&mut self) -> &mut Self {
+        self.config.confirm_twice();
+        self
+    }
+    /// Require at least this many checked items in multi select mode. Confirming with fewer items
+    /// shows a notice below the list and the dialog stays open.
+    ///
+    /// Together with the same `max_selections`, exactly this many items have to be checked.
+    /// If it is larger than `max_selections`, the dialog can only be cancelled.
+    pub fn min_selections(&mut self, min: usize) -> &mut Self {
+        self.config.min_selections(min);
+        self
+    }
+    /// Allow at most this many checked items in multi select mode. Checking another item is rejected
+    /// with a notice below the list until an item is unchecked.
+    pub fn max_selections(&mut self, max: usize) -> &mut Self {
+        self.config.max_selections(max);
+        self
+    }
+    /// Print the title in bold
+    pub fn bold_title(&mut self) -> &mut Self {
+        self.config.bold_title();
+        self
+    }
+    /// Replace the items between two runs of the dialog, for example when the data behind it changed.
+    /// The next `start` shows the new items. The selected index is kept and clamped to the last item,
+    /// checked and disabled indices beyond the last item are dropped. Use `update_items` to keep the selected
+    /// and checked items instead, if the items can be compared.
+    pub fn set_items(&mut self, items: &'a [I]) -> &mut Self {
+        let last_index = items.len().saturating_sub(1);
+
+        self.items = Items::Borrowed(items);
+        self.selected_item = self.selected_item.min(last_index);
+        self.initial_item = self.initial_item.min(last_index);
+        self.checked_items.retain(|&index| index < items.len());
+        self.disabled_items.retain(|&index| index < items.len());
+        self.removed_items.clear();
+        // the lines are built again when the dialog starts
+        self.lines.clear();
+        self.visible_items.clear();
+        self
+    }
+    /// Uses the options of the builder instead of the ones set before
+    pub(crate) fn apply(&mut self, builder: &SelectBuilder) {
+        self.config = builder.clone();
+    }
+    /// Use the pointers, styles and colors of the theme instead of the ones set before.
+    /// The methods for single styles, like `pointer` or `bold_selected_item`, change the theme afterwards.
+    pub fn theme(&mut self, theme: Theme) -> &mut Self {
+        self.config.theme(theme);
+        self
+    }
+    /// Set a custom pointer to show in the select dialog
+    pub fn pointer(&mut self, pointer: char) -> &mut Self {
+        self.config.pointer(pointer);
+        self
+    }
+    /// Set a custom pointer of several characters, for example `=>` or ` ▶ `
+    pub fn pointer_str(&mut self, pointer: impl Into<String>) -> &mut Self {
+        self.config.pointer_str(pointer);
+        self
+    }
+    /// Replace the first key that moves the selection up, which is the up arrow by default.
+    /// Keys added with `add_up_key` are kept.
+    pub fn set_up_key(&mut self, key: KeyCode) -> &mut Self {
+        self.config.set_up_key(key);
+        self
+    }
+    /// Replace the first key that moves the selection down, which is the down arrow by default.
+    /// Keys added with `add_down_key` are kept.
+    pub fn set_down_key(&mut self, key: KeyCode) -> &mut Self {
+        self.config.set_down_key(key);
+        self
+    }
+    /// Use the keys of the key map for all actions instead of the keys set before.
+    /// Fails if enter moves the selection up or down while it confirms the selection.
+    pub fn key_map(&mut self, key_map: KeyMap) -> Result<&mut Self, SelectError> {
+        self.config.key_map(key_map)?;
+        Ok(self)
+    }
+    /// Set a pointer to show in front of the items that are not selected. Without it, blank spaces as wide as the pointer are shown
+    pub fn not_selected_pointer(&mut self, pointer: char) -> &mut Self {
+        self.config.not_selected_pointer(pointer);
+        self
+    }
+    /// Move the selected item one cell to the right
+    pub fn move_selected_item_forward(&mut self) -> &mut Self {
+        self.config.move_selected_item_forward();
+        self
+    }
+    /// Move the selected item the given number of cells to the right. 0 doesn't move it.
+    pub fn indent_selected(&mut self, spaces: usize) -> &mut Self {
+        self.config.indent_selected(spaces);
+        self
+    }
+    /// Print the selected item in the given text color
+    pub fn selected_fg(&mut self, color: Color) -> &mut Self {
+        self.config.selected_fg(color);
+        self
+    }
+    /// Print the selected item on the given background color
+    pub fn selected_bg(&mut self, color: Color) -> &mut Self {
+        self.config.selected_bg(color);
+        self
+    }
+    /// Don't print a pointer in front of the items, so they start at the beginning of the row.
+    /// Use a style like `bold_selected_item` to show which item is selected.
+    pub fn no_pointer(&mut self) -> &mut Self {
+        self.config.no_pointer();
+        self
+    }
+    /// Print the pointer in the given color, while the text of the selected item keeps its own color
+    pub fn pointer_color(&mut self, color: Color) -> &mut Self {
+        self.config.pointer_color(color);
+        self
+    }
+    /// Show a checkbox in front of every item that can be toggled with space.
+    /// Use `start_multi` to get all checked items, which enables this mode automatically.
+    pub fn multi_select(&mut self) -> &mut Self {
+        self.config.multi_select();
+        self
+    }
+    /// Moving up on the first item highlights the last item and moving down on the last item highlights the first one
+    pub fn wrap_around(&mut self) -> &mut Self {
+        self.config.wrap_around();
+        self
+    }
+    /// Split the items into pages of the given size and show one page at a time with `Page 1/4` below it,
+    /// instead of scrolling. Up and down move within the page, page up and page down or left and right switch the page.
+    pub fn paginate(&mut self, per_page: usize) -> &mut Self {
+        self.config.paginate(per_page);
+        self
+    }
+    /// Keep at least this many items visible above and below the selected item while scrolling, like `scrolloff` in vim.
+    /// At the ends of the list fewer items are left, and in small windows it is limited to half of the window.
+    pub fn scroll_off(&mut self, rows: usize) -> &mut Self {
+        self.config.scroll_off(rows);
+        self
+    }
+    /// Set the number of items that page up and page down skip. Default is 10.
+    pub fn page_size(&mut self, page_size: usize) -> &mut Self {
+        self.config.page_size(page_size);
+        self
+    }
+    /// Set the number of items that are shown at once. If the list is longer, it scrolls with the selection.
+    /// By default, as many items are shown as fit on the terminal.
+    pub fn visible_rows(&mut self, rows: usize) -> &mut Self {
+        self.config.visible_rows(rows);
+        self
+    }
+    /// Show at most the given number of items at once, even if more fit on the terminal.
+    /// Unlike `visible_rows`, fewer items are shown if the terminal is too small.
+    pub fn max_visible(&mut self, max_visible: usize) -> &mut Self {
+        self.config.max_visible(max_visible);
+        self
+    }
+    /// Show the position of the selected item, like `12/100`, right aligned below the items while the list scrolls
+    pub fn position_indicator(&mut self) -> &mut Self {
+        self.config.position_indicator();
+        self
+    }
+    pub fn underline_selected_item(&mut self) -> &mut Self {
+        self.config.underline_selected_item();
+        self
+    }
+    /// Swap text and background color of the selected row. This works on terminals with limited colors as well.
+    pub fn reverse_selected_item(&mut self) -> &mut Self {
+        self.config.reverse_selected_item();
+        self
+    }
+    /// Print the text of the selected item in bold. It can be combined with underline and colors.
+    pub fn bold_selected_item(&mut self) -> &mut Self {
+        self.config.bold_selected_item();
+        self
+    }
+    /// Print the items that are not selected dimmed, so the selected item stands out without colors.
+    /// It can be combined with the styles of the selected item.
+    pub fn dim_unselected(&mut self) -> &mut Self {
+        self.config.dim_unselected();
+        self
+    }
+    /// Add a key that moves the selection up. Fails if the key is enter while it confirms the selection.
+    pub fn add_up_key(&mut self, key: KeyCode) -> Result<&mut Self, SelectError> {
+        self.config.add_up_key(key)?;
+        Ok(self)
+    }
+    /// Add a key that moves the selection down. Fails if the key is enter while it confirms the selection.
+    pub fn add_down_key(&mut self, key: KeyCode) -> Result<&mut Self, SelectError> {
+        self.config.add_down_key(key)?;
+        Ok(self)
+    }
+    /// Add all keys that move the selection up, for example from a configuration.
+    /// Fails without adding any key if one of them is enter while it confirms the selection.
+    pub fn up_keys(&mut self, keys: &[KeyCode]) -> Result<&mut Self, SelectError> {
+        self.config.up_keys(keys)?;
+        Ok(self)
+    }
+    /// Add all keys that move the selection down, for example from a configuration.
+    /// Fails without adding any key if one of them is enter while it confirms the selection.
+    pub fn down_keys(&mut self, keys: &[KeyCode]) -> Result<&mut Self, SelectError> {
+        self.config.down_keys(keys)?;
+        Ok(self)
+    }
+    /// Add a key that moves the selection up while the modifiers are held, for example Ctrl+P
+    pub fn add_up_key_with_modifiers(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<&mut Self, SelectError> {
+        self.config.add_up_key_with_modifiers(key, modifiers)?;
+        Ok(self)
+    }
+    /// Add a key that moves the selection down while the modifiers are held, for example Ctrl+N
+    pub fn add_down_key_with_modifiers(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<&mut Self, SelectError> {
+        self.config.add_down_key_with_modifiers(key, modifiers)?;
+        Ok(self)
+    }
+    /// Erase the dialog when it is confirmed or cancelled. The cursor is left on the line where the dialog began,
+    /// so following output is printed in its place.
+    pub fn clear_on_exit(&mut self) -> &mut Self {
+        self.config.clear_on_exit();
+        self
+    }
+    /// Don't print the selected item in color and print title, group headers and help text without styles.
+    /// This is also the case if the `NO_COLOR` environment variable is set.
+    pub fn no_color(&mut self) -> &mut Self {
+        self.config.no_color();
+        self
+    }
+    /// Replace the dialog with a single line when it is confirmed, that shows the prefix and the chosen item,
+    /// for example `✔ Selected: item2`. In multi select mode, the checked items are separated by commas.
+    ///
+    /// The dialog is erased when it is cancelled, without printing a line.
+    pub fn report_selection(&mut self, prefix: impl Into<String>) -> &mut Self {
+        self.config.report_selection(prefix);
+        self
+    }
+    /// Return the item right away if the list has only one, without printing the dialog or changing the terminal.
+    /// The handler is still called with `SelectDialogKey::Confirm`. In multi select mode the item is returned checked.
+    pub fn submit_on_single_item(&mut self) -> &mut Self {
+        self.config.submit_on_single_item();
+        self
+    }
+    /// Return the item at the index right away if the input is not a terminal, for example in tests or CI,
+    /// instead of failing with `SelectError::NotATty`. The index is clamped to the last item.
+    /// Like `submit_on_single_item`, nothing is printed and the handler is called with `SelectDialogKey::Confirm`.
+    pub fn headless(&mut self, index: usize) -> &mut Self {
+        self.config.headless(index);
+        self
+    }
+    /// Print the dialog on the alternate screen of the terminal, like full screen programs do.
+    /// The previous content of the terminal is shown again when the dialog ends, also if it is cancelled.
+    pub fn alternate_screen(&mut self) -> &mut Self {
+        self.config.alternate_screen();
+        self
+    }
+    /// Capture the mouse while the dialog is open. Clicking an item highlights it, clicking it again confirms it.
+    /// The scroll wheel moves the selection.
+    ///
+    /// While the mouse is captured, the terminal can't select text.
+    pub fn mouse(&mut self) -> &mut Self {
+        self.config.mouse();
+        self
+    }
+    /// Print all items in a single row, which reads better for a few options like Yes/No/Cancel.
+    /// Left and right move the selection, besides the up and down keys. Groups and mouse clicks are not supported in this layout.
+    pub fn horizontal(&mut self) -> &mut Self {
+        self.config.horizontal();
+        self
+    }
+    /// Pressing a letter highlights the next item that starts with it, like in the lists of most operating systems.
+    /// Keys that are bound to other actions keep their action.
+    pub fn type_ahead(&mut self) -> &mut Self {
+        self.config.type_ahead();
+        self
+    }
+    /// Use the keys of vim: `j` and `k` move down and up, `g` jumps to the first item and `G` to the last one,
+    /// Ctrl+D and Ctrl+U move down and up by half of the visible rows.
+    /// The keys are added to the keys that are already set.
+    pub fn vim_keys(&mut self) -> &mut Self {
+        self.config.vim_keys();
+        self
+    }
+    /// Tab moves the selection down and Shift+Tab moves it up, like between the fields of a form.
+    /// The keys are added to the keys that are already set.
+    pub fn tab_navigation(&mut self) -> &mut Self {
+        self.config.tab_navigation();
+        self
+    }
+    /// Set the key that confirms the selection instead of enter. Afterwards, enter can be used as up or down key.
+    ///
+    /// Confirm keys are handled before the filter, so a character used as confirm key can't be typed into it.
+    pub fn confirm_key(&mut self, key: KeyCode) -> &mut Self {
+        self.config.confirm_key(key);
+        self
+    }
+    /// Add a key that confirms the selection besides enter, for example the right arrow or `l`
+    pub fn add_confirm_key(&mut self, key: KeyCode) -> &mut Self {
+        self.config.add_confirm_key(key);
+        self
+    }
+}
+
+impl<'a, I, W> Select<'a, I, W>
+where
+    I: PartialEq,
+    W: std::io::Write,
+{
+    /// Set the item that is highlighted when the dialog is printed the first time by its value.
+    /// The first item equal to the value is highlighted. If there is none, the first item is highlighted.
+    ///
+    /// The value is looked up right away. For the items of `from_future`, use `initial_value_owned`.
+    pub fn initial_value(&mut self, value: &I) -> &mut Self {
+        let index = self
+            .items
+            .iter()
+            .position(|item| item == value)
+            .unwrap_or(0);
+        self.initial_index(index)
+    }
+    /// Like `initial_value`, but the value is kept until the items of `from_future` are loaded and looked up then
+    #[cfg(feature = "async")]
+    pub fn initial_value_owned(&mut self, value: I) -> &mut Self {
+        if self.loading.is_none() {
+            return self.initial_value(&value);
+        }
+        self.initial_lookup = Some(Box::new(move |items| {
+            items.iter().position(|item| *item == value).unwrap_or(0)
+        }));
         self
     }
-    fn panic_if_key_is_enter(&self, key: KeyCode) {
-        if key == KeyCode::Enter {
-            panic!("Enter key is not supported as up/down key")
+    /// Replace the items like `set_items`, but keep the selected item highlighted if it is still part of the items.
+    /// Otherwise the selected index is clamped to the last item. Checked and disabled items stay checked and disabled
+    /// wherever they moved, those that are gone are dropped.
+    pub fn update_items(&mut self, items: &'a [I]) -> &mut Self {
+        let new_index = |index: &usize| {
+            self.items
+                .get(*index)
+                .and_then(|old_item| items.iter().position(|item| item == old_item))
+        };
+        let selected = new_index(&self.selected_item);
+        let checked_items = self.checked_items.iter().filter_map(new_index).collect();
+        let disabled_items = self.disabled_items.iter().filter_map(new_index).collect();
+
+        if let Some(index) = selected {
+            self.selected_item = index;
         }
+        self.checked_items = checked_items;
+        self.disabled_items = disabled_items;
+        self.set_items(items)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, I, W> Select<'a, I, W>
+where
+    I: serde::Serialize,
+    W: std::io::Write,
+{
+    /// Write the chosen item as a JSON line like `{"index":1,"item":"item2"}` to the writer when the dialog is confirmed,
+    /// so other programs can parse the result. In multi select mode, a line is written for every checked item.
+    /// Pass stdout here and print the dialog to stderr to use the dialog in scripts.
+    ///
+    /// If the item can't be serialized, `start` fails with `SelectError::Io`.
+    pub fn json_output(&mut self, out: impl std::io::Write + 'a) -> &mut Self {
+        self.json_output = Some(JsonOutput {
+            out: Box::new(out),
+            serialize: serde_json::to_string::<I>,
+        });
+        self
+    }
+}
+
+impl<'a, I, W> Select<'a, I, W>
+where
+    I: Clone,
+    W: std::io::Write,
+{
+    /// Starts the Select Dialog like `start`, but returns a clone of the chosen item.
+    /// The Select Dialog and its items can be dropped right afterwards.
+    pub fn start_owned(&mut self) -> Result<Option<I>, SelectError> {
+        Ok(self.start()?.cloned())
+    }
+}
+
+/// The writer and the serialization of the items for `json_output`
+#[cfg(feature = "serde")]
+struct JsonOutput<'a, I> {
+    out: Box<dyn Write + 'a>,
+    serialize: fn(&I) -> serde_json::Result<String>,
+}
+
+/// Printed while the items of `from_future` are loading
+#[cfg(feature = "async")]
+const LOADING_TEXT: &str = "Loading…";
+
+/// Printed in turn in front of the loading text
+#[cfg(feature = "async")]
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Waits for the future until the timeout elapsed. Returns None if it was not ready until then.
+#[cfg(feature = "async")]
+async fn within<F: Future + Unpin>(future: F, timeout: Duration) -> Option<F::Output> {
+    match futures::future::select(future, Ticks::after(timeout).next()).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+/// Time between two frames of the spinner
+#[cfg(feature = "async")]
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Number of cells the border takes in every row, a line and a space on both sides
+const BORDER_WIDTH: usize = 4;
+
+/// Printed instead of the items if the filter matches none of them
+const NO_MATCHES_TEXT: &str = "No matches";
+
+/// Printed between the items in the horizontal layout
+const HORIZONTAL_SEPARATOR: &str = "  ";
+
+/// Returns the name of the key for hints like "Press enter again to confirm"
+fn key_name(key: &KeyEvent) -> String {
+    let name = match key.code {
+        KeyCode::Char(' ') => String::from("space"),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(number) => format!("F{}", number),
+        code => format!("{:?}", code).to_lowercase(),
+    };
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        format!("Ctrl+{}", name)
+    } else {
+        name
+    }
+}
+
+/// Returns the row that shows whether there are items hidden in one direction, together with its width
+fn scroll_indicator(visible: bool, text: &str) -> (String, usize) {
+    if visible {
+        (text.to_string(), text.width())
+    } else {
+        (String::new(), 0)
+    }
+}
+
+/// Returns the count with the word item in singular or plural
+fn item_count(count: usize) -> String {
+    if count == 1 {
+        "1 item".to_string()
+    } else {
+        format!("{} items", count)
     }
 }
@@ -0,0 +1,64 @@
+use crossterm::cursor::MoveTo;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use std::io::{self, Write};
+
+/// Enables raw mode of the terminal as long as it is alive.
+///
+/// Raw mode is disabled again when the guard is dropped, which also happens when a panic unwinds.
+pub struct RawMode;
+
+impl RawMode {
+    pub fn enable() -> Self {
+        let _ = enable_raw_mode();
+        RawMode
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+    }
+}
+
+/// The modes of the terminal besides raw mode that the dialog switches on: mouse capture and the alternate screen.
+///
+/// Their escape codes are written to the output of the dialog, so they reach the same terminal as the dialog,
+/// also if it is printed to stderr while stdout is redirected.
+pub struct TerminalModes {
+    mouse_capture: bool,
+    alternate_screen: bool,
+}
+
+impl TerminalModes {
+    /// Switches the modes on
+    pub fn enter(
+        out: &mut impl Write,
+        mouse_capture: bool,
+        alternate_screen: bool,
+    ) -> io::Result<Self> {
+        if mouse_capture {
+            execute!(out, EnableMouseCapture)?;
+        }
+        if alternate_screen {
+            execute!(out, EnterAlternateScreen, MoveTo(0, 0))?;
+        }
+        Ok(TerminalModes {
+            mouse_capture,
+            alternate_screen,
+        })
+    }
+    /// Switches the modes off again. The previous content of the terminal is shown again after the alternate screen.
+    pub fn leave(self, out: &mut impl Write) -> io::Result<()> {
+        if self.alternate_screen {
+            execute!(out, LeaveAlternateScreen)?;
+        }
+        if self.mouse_capture {
+            execute!(out, DisableMouseCapture)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crossterm::style::Color;
+
+/// The look of the select dialog: pointers, styles and colors of the selected item.
+///
+/// `Theme::default()` is the look of the dialog without configuration. Define a theme once and pass it to
+/// `Select::theme` to give all dialogs of an application the same look.
+///
+/// ```
+/// use cli_select::{Color, Theme};
+///
+/// let theme = Theme {
+///     pointer: String::from("▶"),
+///     bold_selected_item: true,
+///     pointer_color: Some(Color::Cyan),
+///     ..Theme::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Theme {
+    /// Printed in front of the selected item. Default is `>`
+    pub pointer: String,
+    /// Printed in front of the items that are not selected. Default is a blank space
+    pub not_selected_pointer: Option<char>,
+    /// Don't print pointers, so the selection is only shown with styles
+    pub no_pointer: bool,
+    /// Number of cells the selected item is moved to the right
+    pub selected_indent: usize,
+    /// Underline the text of the selected item, the pointer is not underlined
+    pub underline_selected_item: bool,
+    /// Print the text of the selected item in bold, the pointer is not bold
+    pub bold_selected_item: bool,
+    /// Swap text and background color of the selected item, including the pointer
+    pub reverse_selected_item: bool,
+    /// Print the items that are not selected dimmed
+    pub dim_unselected: bool,
+    /// Text color of the selected item
+    pub selected_fg: Option<Color>,
+    /// Background color of the selected item
+    pub selected_bg: Option<Color>,
+    /// Color of the pointer of the selected item
+    pub pointer_color: Option<Color>,
+    /// Print the title in bold
+    pub bold_title: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            pointer: String::from(">"),
+            not_selected_pointer: None,
+            no_pointer: false,
+            selected_indent: 0,
+            underline_selected_item: false,
+            bold_selected_item: false,
+            reverse_selected_item: false,
+            dim_unselected: false,
+            selected_fg: None,
+            selected_bg: None,
+            pointer_color: None,
+            bold_title: false,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use futures::channel::mpsc::{self, UnboundedReceiver};
+use futures::Stream;
+use std::pin::Pin;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// A stream that yields every time the interval elapsed. It doesn't depend on a specific runtime,
+/// the ticks come from a thread that sleeps in between.
+///
+/// The thread stops right away when the stream is dropped, it doesn't wait for the next tick.
+pub(crate) struct Ticks {
+    receiver: UnboundedReceiver<()>,
+    // dropping the sender wakes the thread up and tells it to stop
+    _stop: Sender<()>,
+}
+
+impl Ticks {
+    /// Creates a stream that yields every time the interval elapsed
+    pub(crate) fn every(interval: Duration) -> Self {
+        Ticks::spawn(interval, false)
+    }
+    /// Creates a stream that yields once after the duration, for example for a timeout
+    pub(crate) fn after(duration: Duration) -> Self {
+        Ticks::spawn(duration, true)
+    }
+    fn spawn(interval: Duration, once: bool) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        let (stop, stopped) = channel::<()>();
+
+        std::thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                if sender.unbounded_send(()).is_err() || once {
+                    break;
+                }
+            }
+        });
+        Ticks {
+            receiver,
+            _stop: stop,
+        }
+    }
+}
+
+impl Stream for Ticks {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
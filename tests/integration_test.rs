@@ -1 +1,491 @@
+use cli_select::{
+    KeyCode, KeyEvent, KeyMap, KeyModifiers, Line, Select, SelectBuilder, SelectError,
+    VecEventSource,
+};
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
 
+#[test]
+fn builder_methods_are_public() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+
+    select
+        .pointer('◉')
+        .not_selected_pointer('○')
+        .set_up_key(KeyCode::Up)
+        .set_down_key(KeyCode::Down)
+        .add_up_key(KeyCode::Char('k'))
+        .unwrap()
+        .add_down_key(KeyCode::Char('j'))
+        .unwrap()
+        .move_selected_item_forward()
+        .underline_selected_item()
+        .on_selection_changed(|_key, item| println!("{}", item));
+}
+
+#[test]
+fn selected_index_is_first_item_before_start() {
+    let items = vec!["item1", "item2", "item3"];
+    let select = Select::new(&items, Vec::new());
+
+    assert_eq!(select.selected_index(), 0);
+}
+
+#[test]
+fn select_accepts_arrays_and_slices() {
+    let items = ["item1", "item2", "item3"];
+
+    Select::new(&items, Vec::new());
+    Select::new(&items[1..], Vec::new());
+}
+
+#[test]
+fn select_can_own_its_items() {
+    let select = Select::from_iter((1..=3).map(|i| format!("item{}", i)), Vec::new());
+
+    assert_eq!(select.selected_index(), 0);
+}
+
+#[test]
+fn enter_is_rejected_as_navigation_key() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+
+    assert_eq!(
+        select.add_up_key(KeyCode::Enter).err(),
+        Some(SelectError::EnterKeyNotSupported)
+    );
+    assert_eq!(
+        select.add_down_key(KeyCode::Enter).err(),
+        Some(SelectError::EnterKeyNotSupported)
+    );
+}
+
+/// An item that can only be displayed, it is neither `Clone` nor `Debug`
+struct DisplayOnly(&'static str);
+
+impl Display for DisplayOnly {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[test]
+fn items_only_need_to_implement_display() {
+    let items = vec![DisplayOnly("item1"), DisplayOnly("item2")];
+    let select = Select::new(&items, Vec::new());
+
+    assert_eq!(select.selected_index(), 0);
+}
+
+#[test]
+fn single_item_is_submitted_without_dialog() {
+    let items = vec!["item1"];
+    let mut select = Select::new(&items, Vec::new());
+    select.submit_on_single_item();
+
+    assert_eq!(select.start(), Ok(Some(&"item1")));
+    assert_eq!(select.start_multi(), Ok(vec![&"item1"]));
+    assert!(select.writer().is_empty());
+}
+
+#[test]
+fn starting_with_empty_list_fails() {
+    let items = Vec::<&str>::new();
+    let mut select = Select::new(&items, Vec::new());
+
+    assert_eq!(select.start().err(), Some(SelectError::EmptyList));
+    assert_eq!(select.start_multi().err(), Some(SelectError::EmptyList));
+    assert_eq!(select.start_owned().err(), Some(SelectError::EmptyList));
+}
+
+#[test]
+fn nothing_is_printed_before_start() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select.title("title").help_text("help");
+
+    assert!(select.writer().is_empty());
+}
+
+#[test]
+fn lines_can_be_styled_outside_of_select() {
+    let mut line = Line::new(String::from("item"), '>');
+    line.select();
+    line.space_from_pointer(2);
+    line.underline();
+
+    assert_eq!(line.to_string(), ">  \x1b[4mitem\x1b[24m");
+    assert_eq!(line.len(), 7);
+}
+
+#[test]
+fn items_without_display_use_label_function() {
+    struct User {
+        name: &'static str,
+    }
+    let users = [User { name: "alice" }, User { name: "bob" }];
+
+    let mut select = Select::with_label(&users, vec![], |user: &User| user.name.to_uppercase());
+    select.initial_index(1);
+
+    assert_eq!(select.selected_index(), 1);
+}
+
+#[test]
+fn selected_item_can_be_inspected_before_start() {
+    let items = ["item1", "item2", "item3"];
+
+    let mut select = Select::new(&items, vec![]);
+    select.initial_index(2);
+
+    assert_eq!(select.selected(), Some(&"item3"));
+    assert_eq!(select.items(), &items);
+
+    let empty: [&str; 0] = [];
+    assert_eq!(Select::new(&empty, vec![]).selected(), None);
+}
+
+#[test]
+fn enter_can_move_if_it_does_not_confirm() {
+    let items = ["item1", "item2"];
+    let mut select = Select::new(&items, vec![]);
+
+    select.confirm_key(KeyCode::Right);
+
+    assert!(select.add_down_key(KeyCode::Enter).is_ok());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn starting_async_with_empty_list_fails() {
+    let items: Vec<&str> = vec![];
+    let mut select = Select::new(&items, vec![]);
+
+    let result = futures::executor::block_on(select.start_async());
+    assert_eq!(result.err(), Some(SelectError::EmptyList));
+
+    let mut select = Select::from_future(futures::future::ready(Vec::<&str>::new()), vec![]);
+
+    let result = futures::executor::block_on(select.start_async());
+    assert_eq!(result.err(), Some(SelectError::EmptyList));
+}
+
+#[test]
+fn initial_value_highlights_equal_item() {
+    let items = ["dev", "staging", "prod"];
+    let mut select = Select::new(&items, vec![]);
+
+    select.initial_value(&"prod");
+    assert_eq!(select.selected_index(), 2);
+
+    select.initial_value(&"local");
+    assert_eq!(select.selected_index(), 0);
+}
+
+#[test]
+fn io_errors_convert_to_select_error() {
+    let error = SelectError::from(std::io::Error::new(
+        std::io::ErrorKind::UnexpectedEof,
+        "input closed",
+    ));
+
+    assert_eq!(
+        error,
+        SelectError::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))
+    );
+    assert_eq!(
+        error.to_string(),
+        "Reading the input or writing the output failed: input closed"
+    );
+    assert_eq!(
+        std::error::Error::source(&error).map(|source| source.to_string()),
+        Some(String::from("input closed"))
+    );
+}
+
+#[test]
+fn builder_creates_dialogs_for_different_items() {
+    let mut builder = SelectBuilder::new();
+    builder.title("Choose").wrap_around();
+
+    let colors = vec!["red", "green"];
+    let sizes = vec!["small", "medium", "large"];
+
+    assert_eq!(builder.items(&colors, Vec::new()).items(), &colors[..]);
+    assert_eq!(builder.items(&sizes, Vec::new()).items(), &sizes[..]);
+}
+
+#[test]
+fn builder_creates_dialogs_for_owned_items_and_items_without_display() {
+    struct Color {
+        name: &'static str,
+    }
+    let keys = [KeyCode::Up, KeyCode::Enter].map(|code| KeyEvent::new(code, KeyModifiers::NONE));
+    let mut builder = SelectBuilder::new();
+    builder.wrap_around();
+
+    let mut select = builder.items_from_iter(["small", "large"].map(String::from), Vec::new());
+    select.event_source(VecEventSource::from_keys(keys));
+    assert_eq!(select.start_owned(), Ok(Some(String::from("large"))));
+
+    let colors = [Color { name: "red" }, Color { name: "green" }];
+    let mut select = builder.items_with_label(&colors, Vec::new(), |color| color.name.to_string());
+    select.event_source(VecEventSource::from_keys(keys));
+    assert_eq!(
+        select.start().unwrap().map(|color| color.name),
+        Some("green")
+    );
+}
+
+#[test]
+fn builder_rejects_enter_as_added_move_key() {
+    assert_eq!(
+        SelectBuilder::new().add_up_key(KeyCode::Enter).err(),
+        Some(SelectError::EnterKeyNotSupported)
+    );
+}
+
+#[test]
+fn builder_rejects_enter_as_move_key() {
+    let mut key_map = KeyMap::default();
+    key_map
+        .up
+        .push(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    assert_eq!(
+        SelectBuilder::new().key_map(key_map).err(),
+        Some(SelectError::EnterKeyNotSupported)
+    );
+}
+
+#[test]
+fn starting_with_key_bound_twice_fails() {
+    let items = vec!["item1", "item2"];
+    let mut select = Select::new(&items, Vec::new());
+    select.add_up_key(KeyCode::Char('x')).unwrap();
+    select.add_confirm_key(KeyCode::Char('x'));
+
+    assert_eq!(
+        select.start().err(),
+        Some(SelectError::ConflictingKeyBinding(KeyEvent::new(
+            KeyCode::Char('x'),
+            KeyModifiers::NONE
+        )))
+    );
+}
+
+/// Returns a source that presses the keys without modifiers
+fn press(keys: &[KeyCode]) -> VecEventSource {
+    VecEventSource::from_keys(
+        keys.iter()
+            .map(|code| KeyEvent::new(*code, KeyModifiers::NONE)),
+    )
+}
+
+#[test]
+fn scripted_keys_navigate_and_confirm() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select.event_source(press(&[
+        KeyCode::Down,
+        KeyCode::Down,
+        KeyCode::Up,
+        KeyCode::Enter,
+    ]));
+
+    assert_eq!(select.start(), Ok(Some(&"item2")));
+}
+
+#[test]
+fn scripted_keys_wrap_around() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .wrap_around()
+        .event_source(press(&[KeyCode::Up, KeyCode::Enter]));
+
+    assert_eq!(select.start(), Ok(Some(&"item3")));
+}
+
+#[test]
+fn scripted_escape_cancels() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select.event_source(press(&[KeyCode::Down, KeyCode::Esc]));
+
+    assert_eq!(select.start(), Ok(None));
+}
+
+#[test]
+fn closed_input_fails_without_panic() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select.event_source(press(&[KeyCode::Down]));
+
+    assert_eq!(
+        select.start(),
+        Err(SelectError::from(std::io::Error::from(
+            std::io::ErrorKind::UnexpectedEof
+        )))
+    );
+}
+
+#[test]
+fn editable_list_returns_remaining_items() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select.event_source(press(&[KeyCode::Delete, KeyCode::Down, KeyCode::Enter]));
+
+    let edited = select.start_editable().unwrap();
+    assert_eq!(edited.remaining, vec![&"item2", &"item3"]);
+    assert_eq!(edited.chosen, Some(&"item3"));
+}
+
+#[test]
+fn starting_with_letter_bound_twice_in_different_case_fails() {
+    let items = vec!["item1", "item2"];
+    let mut select = Select::new(&items, Vec::new());
+    select.add_up_key(KeyCode::Char('k')).unwrap();
+    select.add_confirm_key(KeyCode::Char('K'));
+
+    assert_eq!(
+        select.start().err(),
+        Some(SelectError::ConflictingKeyBinding(KeyEvent::new(
+            KeyCode::Char('k'),
+            KeyModifiers::NONE
+        )))
+    );
+}
+
+#[test]
+fn remove_keys_are_free_unless_editable() {
+    let items = vec!["item1", "item2"];
+    let mut key_map = KeyMap::default();
+    key_map
+        .cancel
+        .push(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .key_map(key_map)
+        .unwrap()
+        .event_source(press(&[KeyCode::Backspace]));
+
+    assert_eq!(select.start(), Ok(None));
+}
+
+#[test]
+fn timeout_confirms_highlighted_item() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .timeout(Duration::from_secs(60))
+        .event_source(press(&[KeyCode::Down]).idle());
+
+    assert_eq!(select.start(), Ok(Some(&"item2")));
+    assert!(select.timed_out());
+}
+
+#[test]
+fn timeout_cancels_if_set() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .timeout(Duration::from_secs(60))
+        .timeout_cancels()
+        .event_source(press(&[KeyCode::Down]).idle());
+
+    assert_eq!(select.start(), Ok(None));
+    assert!(select.timed_out());
+}
+
+#[test]
+fn timeout_does_not_confirm_what_enter_could_not() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .filterable()
+        .timeout(Duration::from_secs(60))
+        .event_source(press(&[KeyCode::Char('x')]).idle());
+
+    assert_eq!(select.start(), Ok(None));
+
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .min_selections(1)
+        .timeout(Duration::from_secs(60))
+        .event_source(VecEventSource::default().idle());
+
+    assert_eq!(select.start_multi(), Ok(vec![]));
+    assert!(select.timed_out());
+}
+
+#[test]
+fn timeout_is_no_closed_input() {
+    let items = vec!["item1", "item2", "item3"];
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .timeout(Duration::from_secs(60))
+        .event_source(press(&[KeyCode::Down]));
+
+    assert_eq!(
+        select.start(),
+        Err(SelectError::from(std::io::Error::from(
+            std::io::ErrorKind::UnexpectedEof
+        )))
+    );
+}
+
+#[test]
+fn removed_item_is_not_chosen_if_only_disabled_items_are_left() {
+    let items = vec!["a", "b"];
+    let keys = [KeyCode::Delete, KeyCode::Enter, KeyCode::Esc]
+        .map(|code| KeyEvent::new(code, KeyModifiers::NONE));
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .disabled_indices(&[1])
+        .event_source(VecEventSource::from_keys(keys));
+
+    let edited = select.start_editable().unwrap();
+    assert_eq!(edited.remaining, vec![&"b"]);
+    assert_eq!(edited.chosen, None);
+}
+
+#[test]
+fn broken_output_fails_with_io_error() {
+    struct BrokenPipe;
+
+    impl std::io::Write for BrokenPipe {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let items = vec!["item1", "item2"];
+    let keys = [KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)];
+    let mut select = Select::new(&items, BrokenPipe);
+    select.event_source(VecEventSource::from_keys(keys));
+
+    assert_eq!(
+        select.start(),
+        Err(SelectError::from(std::io::Error::from(
+            std::io::ErrorKind::BrokenPipe
+        )))
+    );
+}
+
+#[test]
+fn initial_value_can_be_a_temporary() {
+    let items = vec![String::from("local"), String::from("prod")];
+    let keys = [KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)];
+    let mut select = Select::new(&items, Vec::new());
+    select
+        .initial_value(&String::from("prod"))
+        .event_source(VecEventSource::from_keys(keys));
+
+    assert_eq!(select.start(), Ok(Some(&items[1])));
+}